@@ -0,0 +1,32 @@
+//! The errors reported when an 'Array2<T>' cannot be built
+
+use std::fmt;
+
+/// The reason a 2d array could not be built from the given elements
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Array2Error {
+    /// The amount of elements does not match the size of the 2d array
+    LengthMismatch {
+        width: usize,
+        height: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// The 2d array is wider than the rows the elements are read from
+    WidthExceedsSource { width: usize, source_width: usize },
+}
+
+impl fmt::Display for Array2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Array2Error::LengthMismatch { width, height, expected, actual } => write!(
+                f, "a {}x{} array needs {} elements, but {} were given", width, height, expected, actual
+            ),
+            Array2Error::WidthExceedsSource { width, source_width } => write!(
+                f, "an array {} columns wide cannot be taken from rows of {} elements", width, source_width
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Array2Error {}
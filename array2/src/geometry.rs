@@ -0,0 +1,260 @@
+//! Operations that move the elements of an 'Array2<T>' around, such as
+//! rotating, mirroring, cropping, and padding it
+
+use crate::Array2;
+
+/// How to fill the positions that lie past the edge of a 2d array
+#[derive(Clone, PartialEq, Debug)]
+pub enum EdgeMode<T> {
+    /// Fill every position past the edge with the same value
+    Constant(T),
+    /// Repeat the nearest element on the edge, so `a b c` continues as `c c c`
+    Replicate,
+    /// Mirror the elements around the edge without repeating it, so `a b c`
+    /// continues as `b a b`
+    Reflect,
+}
+
+impl<T> EdgeMode<T> {
+    // Returns which index of a side of `length` elements the position `index` reads
+    // from, where negative positions and positions from `length` on lie past the
+    // edge, or None if the position takes the constant value
+    pub(crate) fn source_index(&self, index: isize, length: usize) -> Option<usize> {
+        let length = length as isize;
+        if (0..length).contains(&index) {
+            return Some(index as usize);
+        }
+        match self {
+            EdgeMode::Constant(_) => None,
+            EdgeMode::Replicate => Some(index.clamp(0, length - 1) as usize),
+            // mirroring around both edges repeats every 2 * (length - 1) positions
+            EdgeMode::Reflect if length == 1 => Some(0),
+            EdgeMode::Reflect => {
+                let period = 2 * (length - 1);
+                let offset = index.rem_euclid(period);
+                Some(offset.min(period - offset) as usize)
+            }
+        }
+    }
+}
+
+impl<T: Clone> Array2<T> {
+    /// Returns a new 2d array with rows and columns swapped, so the element at
+    /// (row, column) moves to (column, row)
+    pub fn transpose(&self) -> Array2<T> {
+        Array2::from_fn(self.height, self.width, |row_idx, column_idx| self[(column_idx, row_idx)].clone())
+    }
+
+    /// Returns a new 2d array turned a quarter turn clockwise
+    pub fn rotate90(&self) -> Array2<T> {
+        Array2::from_fn(self.height, self.width, |row_idx, column_idx| {
+            self[(self.height - 1 - column_idx, row_idx)].clone()
+        })
+    }
+
+    /// Returns a new 2d array turned a half turn
+    pub fn rotate180(&self) -> Array2<T> {
+        Array2::from_fn(self.width, self.height, |row_idx, column_idx| {
+            self[(self.height - 1 - row_idx, self.width - 1 - column_idx)].clone()
+        })
+    }
+
+    /// Returns a new 2d array turned a quarter turn counterclockwise, which is
+    /// three quarter turns clockwise
+    pub fn rotate270(&self) -> Array2<T> {
+        Array2::from_fn(self.height, self.width, |row_idx, column_idx| {
+            self[(column_idx, self.width - 1 - row_idx)].clone()
+        })
+    }
+
+    /// Returns a new 2d array mirrored left to right
+    pub fn flip_horizontal(&self) -> Array2<T> {
+        Array2::from_fn(self.width, self.height, |row_idx, column_idx| {
+            self[(row_idx, self.width - 1 - column_idx)].clone()
+        })
+    }
+
+    /// Returns a new 2d array mirrored top to bottom
+    pub fn flip_vertical(&self) -> Array2<T> {
+        Array2::from_fn(self.width, self.height, |row_idx, column_idx| {
+            self[(self.height - 1 - row_idx, column_idx)].clone()
+        })
+    }
+
+    /// Returns a copy of a rectangle of the 2d array
+    ///
+    /// # Arguments
+    ///
+    /// * 'row': the row of the top left element of the rectangle
+    /// * 'col': the column of the top left element of the rectangle
+    /// * 'height': the amount of rows in the rectangle
+    /// * 'width': the amount of columns in the rectangle
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle does not fit inside the 2d array
+    pub fn crop(&self, row: usize, col: usize, height: usize, width: usize) -> Array2<T> {
+        self.view(row, col, height, width).to_array2()
+    }
+
+    /// Returns a new 2d array with extra rows and columns added around the edges,
+    /// filled as `mode` asks
+    ///
+    /// # Arguments
+    ///
+    /// * 'top': the amount of rows to add above the 2d array
+    /// * 'bottom': the amount of rows to add below the 2d array
+    /// * 'left': the amount of columns to add left of the 2d array
+    /// * 'right': the amount of columns to add right of the 2d array
+    /// * 'mode': how to fill the added rows and columns
+    ///
+    /// # Panics
+    ///
+    /// Panics if rows or columns are added to an empty side of the 2d array with a
+    /// mode that needs elements to copy from
+    pub fn pad(&self, top: usize, bottom: usize, left: usize, right: usize, mode: &EdgeMode<T>) -> Array2<T> {
+        if !matches!(mode, EdgeMode::Constant(_)) {
+            assert!((self.height > 0 || top + bottom == 0) && (self.width > 0 || left + right == 0),
+                "cannot extend the edges of a {}x{} array", self.width, self.height);
+        }
+        Array2::from_fn(left + self.width + right, top + self.height + bottom, |row_idx, column_idx| {
+            let source_row = mode.source_index(row_idx as isize - top as isize, self.height);
+            let source_col = mode.source_index(column_idx as isize - left as isize, self.width);
+            match (source_row, source_col, mode) {
+                (Some(source_row), Some(source_col), _) => self[(source_row, source_col)].clone(),
+                (_, _, EdgeMode::Constant(value)) => value.clone(),
+                _ => unreachable!("only constant padding leaves a position without a source"),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{grid, SHAPES};
+
+    fn shape(array: &Array2<usize>) -> (usize, usize, Vec<usize>) {
+        (array.width(), array.height(), array.as_slice().to_vec())
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        assert_eq!(shape(&grid(3, 2).transpose()), (2, 3, vec![0, 10, 1, 11, 2, 12]));
+        for (width, height) in SHAPES {
+            let array = grid(width, height);
+            let transposed = array.transpose();
+            assert_eq!((transposed.width(), transposed.height()), (height, width));
+            for (row_idx, column_idx, val) in array.iter_row_major() {
+                assert_eq!(transposed[(column_idx, row_idx)], *val);
+            }
+            assert_eq!(shape(&transposed.transpose()), shape(&array));
+        }
+    }
+
+    #[test]
+    fn rotations_turn_clockwise() {
+        let array = grid(3, 2);
+        assert_eq!(shape(&array.rotate90()), (2, 3, vec![10, 0, 11, 1, 12, 2]));
+        assert_eq!(shape(&array.rotate180()), (3, 2, vec![12, 11, 10, 2, 1, 0]));
+        assert_eq!(shape(&array.rotate270()), (2, 3, vec![2, 12, 1, 11, 0, 10]));
+        for (width, height) in SHAPES {
+            let array = grid(width, height);
+            assert_eq!(shape(&array.rotate90().rotate90()), shape(&array.rotate180()));
+            assert_eq!(shape(&array.rotate90().rotate180()), shape(&array.rotate270()));
+            assert_eq!(shape(&array.rotate270().rotate90()), shape(&array));
+            assert_eq!(shape(&array.rotate90()), shape(&array.transpose().flip_horizontal()));
+        }
+    }
+
+    #[test]
+    fn flips_mirror_the_array() {
+        let array = grid(2, 3);
+        assert_eq!(shape(&array.flip_horizontal()), (2, 3, vec![1, 0, 11, 10, 21, 20]));
+        assert_eq!(shape(&array.flip_vertical()), (2, 3, vec![20, 21, 10, 11, 0, 1]));
+        for (width, height) in SHAPES {
+            let array = grid(width, height);
+            assert_eq!(shape(&array.flip_horizontal().flip_horizontal()), shape(&array));
+            assert_eq!(shape(&array.flip_vertical().flip_vertical()), shape(&array));
+            assert_eq!(shape(&array.flip_horizontal().flip_vertical()), shape(&array.rotate180()));
+        }
+    }
+
+    #[test]
+    fn crop_copies_the_rectangle() {
+        assert_eq!(shape(&grid(3, 2).crop(1, 1, 1, 2)), (2, 1, vec![11, 12]));
+        assert_eq!(shape(&grid(2, 3).crop(0, 1, 3, 1)), (1, 3, vec![1, 11, 21]));
+        assert_eq!(shape(&grid(1, 4).crop(1, 0, 2, 1)), (1, 2, vec![10, 20]));
+        assert_eq!(shape(&grid(0, 3).crop(1, 0, 2, 0)), (0, 2, vec![]));
+        assert_eq!(shape(&grid(3, 0).crop(0, 1, 0, 2)), (2, 0, vec![]));
+        for (width, height) in SHAPES {
+            assert_eq!(shape(&grid(width, height).crop(0, 0, height, width)), shape(&grid(width, height)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn crop_outside_the_array_panics() {
+        grid(2, 3).crop(2, 1, 2, 1);
+    }
+
+    #[test]
+    fn pad_with_a_constant() {
+        assert_eq!(shape(&grid(2, 3).pad(1, 0, 0, 1, &EdgeMode::Constant(99))),
+            (3, 4, vec![99, 99, 99, 0, 1, 99, 10, 11, 99, 20, 21, 99]));
+        // constant padding needs nothing to copy from, so it works on empty arrays too
+        assert_eq!(shape(&grid(0, 3).pad(0, 0, 1, 1, &EdgeMode::Constant(7))), (2, 3, vec![7; 6]));
+        assert_eq!(shape(&grid(3, 0).pad(1, 0, 0, 0, &EdgeMode::Constant(7))), (3, 1, vec![7; 3]));
+        assert_eq!(shape(&grid(0, 0).pad(1, 1, 1, 1, &EdgeMode::Constant(7))), (2, 2, vec![7; 4]));
+    }
+
+    #[test]
+    fn pad_by_replicating_the_edge() {
+        assert_eq!(shape(&grid(3, 2).pad(1, 1, 2, 1, &EdgeMode::Replicate)), (6, 4, vec![
+            0, 0, 0, 1, 2, 2,
+            0, 0, 0, 1, 2, 2,
+            10, 10, 10, 11, 12, 12,
+            10, 10, 10, 11, 12, 12,
+        ]));
+        assert_eq!(shape(&grid(1, 4).pad(0, 0, 2, 1, &EdgeMode::Replicate)),
+            (4, 4, vec![0, 0, 0, 0, 10, 10, 10, 10, 20, 20, 20, 20, 30, 30, 30, 30]));
+    }
+
+    #[test]
+    fn pad_by_reflecting_around_the_edge() {
+        assert_eq!(shape(&grid(2, 3).pad(2, 1, 1, 0, &EdgeMode::Reflect)), (3, 6, vec![
+            21, 20, 21,
+            11, 10, 11,
+            1, 0, 1,
+            11, 10, 11,
+            21, 20, 21,
+            11, 10, 11,
+        ]));
+        // the reflection repeats as far as the padding reaches
+        assert_eq!(shape(&grid(3, 1).pad(0, 0, 5, 5, &EdgeMode::Reflect)),
+            (13, 1, vec![1, 0, 1, 2, 1, 0, 1, 2, 1, 0, 1, 2, 1]));
+        // a single element has nothing to mirror, so it is repeated
+        assert_eq!(shape(&grid(1, 4).pad(0, 0, 1, 1, &EdgeMode::Reflect)),
+            (3, 4, vec![0, 0, 0, 10, 10, 10, 20, 20, 20, 30, 30, 30]));
+    }
+
+    #[test]
+    fn pad_without_padding_leaves_empty_arrays_alone() {
+        for mode in [EdgeMode::Replicate, EdgeMode::Reflect] {
+            assert_eq!(shape(&grid(0, 3).pad(1, 1, 0, 0, &mode)), (0, 5, vec![]));
+            assert_eq!(shape(&grid(3, 0).pad(0, 0, 1, 1, &mode)), (5, 0, vec![]));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot extend the edges of a 0x3 array")]
+    fn replicating_an_empty_side_panics() {
+        grid(0, 3).pad(0, 0, 1, 0, &EdgeMode::Replicate);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot extend the edges of a 3x0 array")]
+    fn reflecting_an_empty_side_panics() {
+        grid(3, 0).pad(0, 1, 0, 0, &EdgeMode::Reflect);
+    }
+}
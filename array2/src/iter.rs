@@ -0,0 +1,318 @@
+//! Iterators over the elements of an 'Array2<T>' in row major and column
+//! major order. Every iterator yields (row, column, value) tuples and walks
+//! the elements where they are stored, without collecting them first.
+
+use std::iter::Enumerate;
+use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
+
+/// An iterator over references to the elements of a 2d array in row major order
+pub struct IterRowMajor<'a, T> {
+    elements: Enumerate<std::slice::Iter<'a, T>>,
+    width: usize,
+}
+
+impl<'a, T> IterRowMajor<'a, T> {
+    pub(crate) fn new(data: &'a [T], width: usize) -> Self {
+        IterRowMajor { elements: data.iter().enumerate(), width }
+    }
+}
+
+impl<'a, T> Iterator for IterRowMajor<'a, T> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next().map(|(index, val)| (index / self.width, index % self.width, val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IterRowMajor<'_, T> {}
+
+/// An iterator over mutable references to the elements of a 2d array in row major order
+pub struct IterRowMajorMut<'a, T> {
+    elements: Enumerate<std::slice::IterMut<'a, T>>,
+    width: usize,
+}
+
+impl<'a, T> IterRowMajorMut<'a, T> {
+    pub(crate) fn new(data: &'a mut [T], width: usize) -> Self {
+        IterRowMajorMut { elements: data.iter_mut().enumerate(), width }
+    }
+}
+
+impl<'a, T> Iterator for IterRowMajorMut<'a, T> {
+    type Item = (usize, usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next().map(|(index, val)| (index / self.width, index % self.width, val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IterRowMajorMut<'_, T> {}
+
+/// An iterator that moves the elements out of a 2d array in row major order
+pub struct IntoIterRowMajor<T> {
+    elements: Enumerate<std::vec::IntoIter<T>>,
+    width: usize,
+}
+
+impl<T> IntoIterRowMajor<T> {
+    pub(crate) fn new(data: Vec<T>, width: usize) -> Self {
+        IntoIterRowMajor { elements: data.into_iter().enumerate(), width }
+    }
+}
+
+impl<T> Iterator for IntoIterRowMajor<T> {
+    type Item = (usize, usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next().map(|(index, val)| (index / self.width, index % self.width, val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIterRowMajor<T> {}
+
+// The position in column major order of the next element an iterator yields, and the
+// size of the array it walks
+#[derive(Clone, Copy)]
+struct ColumnCursor {
+    index: usize,
+    width: usize,
+    height: usize,
+}
+
+impl ColumnCursor {
+    // Moves to the next element, returning its row and column along with where it is
+    // stored in the row major data, or None once every element has been visited
+    fn advance(&mut self) -> Option<(usize, usize, usize)> {
+        if self.index >= self.width * self.height {
+            return None;
+        }
+        let (row_idx, column_idx) = (self.index % self.height, self.index / self.height);
+        self.index += 1;
+        Some((row_idx, column_idx, row_idx * self.width + column_idx))
+    }
+
+    fn remaining(&self) -> usize {
+        self.width * self.height - self.index
+    }
+}
+
+/// An iterator over references to the elements of a 2d array in column major order
+pub struct IterColMajor<'a, T> {
+    data: &'a [T],
+    cursor: ColumnCursor,
+}
+
+impl<'a, T> IterColMajor<'a, T> {
+    pub(crate) fn new(data: &'a [T], width: usize, height: usize) -> Self {
+        IterColMajor { data, cursor: ColumnCursor { index: 0, width, height } }
+    }
+}
+
+impl<'a, T> Iterator for IterColMajor<'a, T> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        self.cursor.advance().map(|(row_idx, column_idx, index)| (row_idx, column_idx, &data[index]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining(), Some(self.cursor.remaining()))
+    }
+}
+
+impl<T> ExactSizeIterator for IterColMajor<'_, T> {}
+
+/// An iterator over mutable references to the elements of a 2d array in column major order
+pub struct IterColMajorMut<'a, T> {
+    data: *mut T,
+    cursor: ColumnCursor,
+    _borrow: PhantomData<&'a mut T>,
+}
+
+// The iterator only hands out references to the elements it mutably borrows, so it can
+// cross threads whenever a mutable slice of them could
+unsafe impl<T: Send> Send for IterColMajorMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterColMajorMut<'_, T> {}
+
+impl<'a, T> IterColMajorMut<'a, T> {
+    pub(crate) fn new(data: &'a mut [T], width: usize, height: usize) -> Self {
+        assert!(data.len() == width * height, "the data does not fill a {}x{} array", width, height);
+        IterColMajorMut { data: data.as_mut_ptr(), cursor: ColumnCursor { index: 0, width, height }, _borrow: PhantomData }
+    }
+}
+
+impl<'a, T> Iterator for IterColMajorMut<'a, T> {
+    type Item = (usize, usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.advance().map(|(row_idx, column_idx, index)| {
+            // SAFETY: the cursor visits every index below `width * height` exactly once,
+            // and the data borrowed for 'a holds that many elements, so each reference
+            // is in bounds and never aliases another one handed out
+            (row_idx, column_idx, unsafe { &mut *self.data.add(index) })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining(), Some(self.cursor.remaining()))
+    }
+}
+
+impl<T> ExactSizeIterator for IterColMajorMut<'_, T> {}
+
+/// An iterator that moves the elements out of a 2d array in column major order
+pub struct IntoIterColMajor<T> {
+    // every element the cursor has not reached yet is initialized
+    data: Vec<MaybeUninit<T>>,
+    cursor: ColumnCursor,
+}
+
+impl<T> IntoIterColMajor<T> {
+    pub(crate) fn new(data: Vec<T>, width: usize, height: usize) -> Self {
+        assert!(data.len() == width * height, "the data does not fill a {}x{} array", width, height);
+        let mut data = ManuallyDrop::new(data);
+        // SAFETY: MaybeUninit<T> has the same size and alignment as T, and the original
+        // vector is never used or dropped again
+        let data = unsafe {
+            Vec::from_raw_parts(data.as_mut_ptr() as *mut MaybeUninit<T>, data.len(), data.capacity())
+        };
+        IntoIterColMajor { data, cursor: ColumnCursor { index: 0, width, height } }
+    }
+}
+
+impl<T> Iterator for IntoIterColMajor<T> {
+    type Item = (usize, usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.advance().map(|(row_idx, column_idx, index)| {
+            // SAFETY: the cursor reaches every element exactly once, so this element is
+            // still initialized and is never read again
+            (row_idx, column_idx, unsafe { self.data[index].assume_init_read() })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining(), Some(self.cursor.remaining()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIterColMajor<T> {}
+
+impl<T> Drop for IntoIterColMajor<T> {
+    fn drop(&mut self) {
+        // drops the elements that were never yielded
+        while let Some((_, _, index)) = self.cursor.advance() {
+            // SAFETY: the cursor has not reached this element before, so it is initialized
+            unsafe { self.data[index].assume_init_drop() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{grid, SHAPES};
+    use crate::Array2;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // Counts how many times it has been dropped, across all of its clones
+    #[derive(Clone)]
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn column_major_iterators_agree() {
+        assert_eq!(grid(3, 2).iter_col_major().map(|(_, _, val)| *val).collect::<Vec<_>>(), vec![0, 10, 1, 11, 2, 12]);
+        for (width, height) in SHAPES {
+            let expected: Vec<(usize, usize, usize)> = grid(width, height).iter_col_major().map(|(r, c, val)| (r, c, *val)).collect();
+            assert_eq!(expected.len(), width * height);
+            let mut array = grid(width, height);
+            assert_eq!(array.iter_col_major_mut().map(|(r, c, val)| (r, c, *val)).collect::<Vec<_>>(), expected);
+            assert_eq!(array.into_iter_col_major().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn column_major_iterators_know_their_length() {
+        for (width, height) in SHAPES {
+            let mut array = grid(width, height);
+            let mut iter = array.iter_col_major_mut();
+            for remaining in (0..=width * height).rev() {
+                assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+                assert_eq!(iter.len(), remaining);
+                iter.next();
+            }
+            assert!(iter.next().is_none());
+            let mut iter = array.into_iter_col_major();
+            for remaining in (0..=width * height).rev() {
+                assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+                iter.next();
+            }
+            assert!(iter.next().is_none());
+        }
+    }
+
+    #[test]
+    fn iter_col_major_mut_writes_through() {
+        let mut array = grid(3, 2);
+        for (order, (_, _, val)) in array.iter_col_major_mut().enumerate() {
+            *val = order;
+        }
+        assert_eq!(array.as_slice(), &[0, 2, 4, 1, 3, 5]);
+        // every reference handed out can be held at once, as none of them alias
+        let mut array = grid(2, 3);
+        let mut references: Vec<&mut usize> = array.iter_col_major_mut().map(|(_, _, val)| val).collect();
+        references.iter_mut().for_each(|val| **val += 1);
+        assert_eq!(array.as_slice(), &[1, 2, 11, 12, 21, 22]);
+    }
+
+    #[test]
+    fn into_iter_col_major_drops_every_element_once() {
+        for (width, height) in SHAPES {
+            for taken in 0..=width * height {
+                let drops = Rc::new(Cell::new(0));
+                let array = Array2::from_fn(width, height, |_, _| DropCounter(drops.clone()));
+                let mut iter = array.into_iter_col_major();
+                for _ in 0..taken {
+                    drop(iter.next());
+                }
+                assert_eq!(drops.get(), taken);
+                drop(iter);
+                assert_eq!(drops.get(), width * height);
+            }
+        }
+    }
+
+    #[test]
+    fn into_iter_col_major_returns_the_elements_it_moves_out() {
+        let array = Array2::from_fn(2, 2, |row_idx, column_idx| Rc::new(row_idx * 2 + column_idx));
+        let first = array.get(0, 0).unwrap().clone();
+        let mut iter = array.into_iter_col_major();
+        let (_, _, moved) = iter.next().unwrap();
+        assert!(Rc::ptr_eq(&moved, &first));
+        drop(iter);
+        assert_eq!(Rc::strong_count(&first), 2);
+        drop(moved);
+        assert_eq!(Rc::strong_count(&first), 1);
+    }
+}
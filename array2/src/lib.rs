@@ -2,11 +2,39 @@
 //! A two dimensional polymorphic array written as,
 //! 'Array2<T>'
 
-/// A struct containing a nested vector of generic type T,
-/// a width, and a height
+use std::ops::{Index, IndexMut};
+
+mod error;
+mod geometry;
+mod iter;
+mod neighbourhood;
+#[cfg(feature = "rayon")]
+mod par;
+mod tile;
+mod view;
+#[cfg(test)]
+pub(crate) mod test_util;
+pub use error::Array2Error;
+pub use geometry::EdgeMode;
+pub use iter::{IntoIterColMajor, IntoIterRowMajor, IterColMajor, IterColMajorMut, IterRowMajor, IterRowMajorMut};
+pub use neighbourhood::Window;
+pub use tile::{EdgePolicy, Tile};
+pub use view::{Column, ColumnMut, View, ViewMut};
+
+// Checks that `length` elements exactly fill a width x height array
+fn check_length(length: usize, width: usize, height: usize) -> Result<(), Array2Error> {
+    if length == width * height {
+        Ok(())
+    } else {
+        Err(Array2Error::LengthMismatch { width, height, expected: width * height, actual: length })
+    }
+}
+
+/// A struct containing a flat vector of generic type T, stored in
+/// row-major order, a width, and a height
 #[derive(Clone)]
 pub struct Array2<T: Clone> {
-    array_2d: Vec<Vec<T>>,
+    data: Vec<T>,
     width: usize,
     height: usize,
 }
@@ -24,62 +52,119 @@ impl<T: Clone> Array2<T> {
         self.height
     }
 
-    /// Constructs a new 2d array from a vector, read in from row major form
+    /// Constructs a new 2d array from a vector, read in from row major form.
     ///
     /// # Arguments
     ///
     /// * 'elements': a vector containing elements of generic type
     /// * 'width': a usize containing the amount of columns in the 2d array
     /// * 'height': a usize containing the amount of rows in the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if `elements` does not hold exactly `width * height` elements
     pub fn from_row_major(elements: Vec<T>, width: usize, height: usize) -> Self {
-        let mut nested_vec: Vec<_> = vec![];
-        for i in 0..height {
-            // Any Array2 created by row-major form will first be stored in columns 0 - width
-            // and then rows 0 - height.
-            nested_vec.push(
-                elements
-                    .iter()
-                    .skip(i * width)
-                    .map(|val| val.clone())
-                    .take(width)
-                    .collect(),
-            );
-        }
+        Array2::try_from_row_major(elements, width, height).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Constructs a new 2d array from a vector, read in from row major form,
+    /// or returns an error if `elements` does not hold exactly `width * height`
+    /// elements
+    ///
+    /// # Arguments
+    ///
+    /// * 'elements': a vector containing elements of generic type
+    /// * 'width': a usize containing the amount of columns in the 2d array
+    /// * 'height': a usize containing the amount of rows in the 2d array
+    pub fn try_from_row_major(elements: Vec<T>, width: usize, height: usize) -> Result<Self, Array2Error> {
+        check_length(elements.len(), width, height)?;
+        // Row-major form is already the order the elements are stored in.
         // Any Array2 will have an associated width and height.
-        Array2 {
-            array_2d: nested_vec,
+        Ok(Array2 {
+            data: elements,
             width,
             height,
+        })
+    }
+
+    /// Constructs a new 2d array from the top left corner of a larger grid of
+    /// elements stored in row major form, dropping the columns past `width` at
+    /// the end of every row and the rows past `height`
+    ///
+    /// # Arguments
+    ///
+    /// * 'elements': a vector containing the grid in row major form
+    /// * 'source_width': a usize containing the amount of columns in the grid
+    /// * 'width': a usize containing the amount of columns in the 2d array
+    /// * 'height': a usize containing the amount of rows in the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is larger than `source_width`, or the grid holds fewer
+    /// than `height` rows
+    pub fn from_row_major_truncating(elements: Vec<T>, source_width: usize, width: usize, height: usize) -> Self {
+        if width > source_width {
+            panic!("{}", Array2Error::WidthExceedsSource { width, source_width });
+        }
+        if elements.len() < source_width * height {
+            panic!("{}", Array2Error::LengthMismatch {
+                width: source_width,
+                height,
+                expected: source_width * height,
+                actual: elements.len(),
+            });
         }
+        if width == source_width {
+            let mut elements = elements;
+            elements.truncate(width * height);
+            return Array2::from_row_major(elements, width, height);
+        }
+        let data = elements
+            .chunks(source_width)
+            .take(height)
+            .flat_map(|row| row[..width].iter().cloned())
+            .collect();
+        Array2::from_row_major(data, width, height)
     }
 
-    /// Constructs a new 2d array from a vector, read in from col major form
+    /// Constructs a new 2d array from a vector, read in from col major form.
     ///
     /// # Arguments
     ///
     /// * 'elements': a vector containing elements of generic type
     /// * 'width': a usize containing the amount of columns in the 2d array
     /// * 'height': a usize containing the amount of rows in the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if `elements` does not hold exactly `width * height` elements
     pub fn from_col_major(elements: Vec<T>, width: usize, height: usize) -> Self {
-        let mut nested_vec: Vec<_> = vec![];
-        for i in 0..height {
+        Array2::try_from_col_major(elements, width, height).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Constructs a new 2d array from a vector, read in from col major form,
+    /// or returns an error if `elements` does not hold exactly `width * height`
+    /// elements
+    ///
+    /// # Arguments
+    ///
+    /// * 'elements': a vector containing elements of generic type
+    /// * 'width': a usize containing the amount of columns in the 2d array
+    /// * 'height': a usize containing the amount of rows in the 2d array
+    pub fn try_from_col_major(elements: Vec<T>, width: usize, height: usize) -> Result<Self, Array2Error> {
+        check_length(elements.len(), width, height)?;
+        let mut data = Vec::with_capacity(width * height);
+        for row_idx in 0..height {
             // Any Array2 created by column-major form will first be stored in rows 0 - height
-            // and then columns 0 - width.
-            nested_vec.push(
-                elements
-                    .iter()
-                    .skip(i)
-                    .step_by(height)
-                    .map(|val| val.clone())
-                    .collect(),
-            );
+            // and then columns 0 - width, so each row is every height-th element.
+            data.extend(elements.iter().skip(row_idx).step_by(height).cloned());
         }
         // Any Array2 will have an associated width and height.
-        Array2 {
-            array_2d: nested_vec,
+        Ok(Array2 {
+            data,
             width,
             height,
-        }
+        })
     }
 
     /// Constructs a new 2d array that has a single value copied at each element
@@ -87,7 +172,7 @@ impl<T: Clone> Array2<T> {
     /// # Arguments
     ///
     /// * 'singular_value': the value that will be copied to each element in
-    ///  the 2d array
+    ///   the 2d array
     /// * 'width': a usize containing the amount of columns in the 2d array
     /// * 'height': a usize containing the amount of rows in the 2d array
     pub fn from_blank_state(singular_value: T, width: usize, height: usize) -> Self {
@@ -98,51 +183,143 @@ impl<T: Clone> Array2<T> {
         Array2::from_row_major(vec_singular_value, width, height)
     }
 
+    /// Constructs a new 2d array by calling `f` with the row and column of each
+    /// element, in row major order
+    ///
+    /// # Arguments
+    ///
+    /// * 'width': a usize containing the amount of columns in the 2d array
+    /// * 'height': a usize containing the amount of rows in the 2d array
+    /// * 'f': a function from the (row, column) of an element to its value
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..width * height).map(|index| f(index / width, index % width)).collect();
+        Array2 {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Returns a new 2d array of the same size holding `f` applied to every element
+    ///
+    /// # Arguments
+    ///
+    /// * 'f': a function from an element to its new value
+    pub fn map<U: Clone>(&self, f: impl FnMut(&T) -> U) -> Array2<U> {
+        Array2 {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns a new 2d array of the same size holding `f` applied to the row,
+    /// column, and value of every element
+    ///
+    /// # Arguments
+    ///
+    /// * 'f': a function from the (row, column, value) of an element to its new value
+    pub fn map_with_index<U: Clone>(&self, mut f: impl FnMut(usize, usize, &T) -> U) -> Array2<U> {
+        Array2 {
+            data: self.iter_row_major().map(|(row_idx, column_idx, val)| f(row_idx, column_idx, val)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns a new 2d array holding `f` applied to each pair of elements at the
+    /// same position in this 2d array and `other`
+    ///
+    /// # Arguments
+    ///
+    /// * 'other': a 2d array of the same size
+    /// * 'f': a function from the pair of elements to the new value
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is not the same size
+    pub fn zip_with<U: Clone, V: Clone>(&self, other: &Array2<U>, mut f: impl FnMut(&T, &U) -> V) -> Array2<V> {
+        assert!(self.width == other.width && self.height == other.height,
+            "cannot zip a {}x{} array with a {}x{} array", self.width, self.height, other.width, other.height);
+        Array2 {
+            data: self.data.iter().zip(&other.data).map(|(a, b)| f(a, b)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Combines every element into a single value in row major order, starting
+    /// from `init`
+    ///
+    /// # Arguments
+    ///
+    /// * 'init': the starting value
+    /// * 'f': a function from the value so far and the next element to the new value
+    pub fn fold<B>(&self, init: B, f: impl FnMut(B, &T) -> B) -> B {
+        self.data.iter().fold(init, f)
+    }
+
     /// Returns an iterator that will iterate over the 2d array in row major
     /// order. The tuple is set up as (row, column, value)
-    pub fn iter_row_major(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+    pub fn iter_row_major(&self) -> IterRowMajor<'_, T> {
         // Any Array2 must be able to be iterated over by row major
-        // utilized from Professor Daniel's Array2
-        self.array_2d
-            .iter()
-            .flatten()
-            .enumerate()
-            .map(move |(index, val)| (index / self.width, index % self.width, val))
+        IterRowMajor::new(&self.data, self.width)
     }
 
     /// Returns an mutable iterator that will iterate over the 2d array in row major
     /// order. The tuple is set up as (row, column, value)
-    pub fn iter_row_major_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut T)> {
+    pub fn iter_row_major_mut(&mut self) -> IterRowMajorMut<'_, T> {
         // Any Array2 must be able to be iterated over by row major
-        // utilized from Professor Daniel's Array2
-        self.array_2d
-            .iter_mut()
-            .flatten()
-            .enumerate()
-            .map(|(index, val)| (index / self.width, index % self.width, val))
+        IterRowMajorMut::new(&mut self.data, self.width)
+    }
+
+    /// Consumes the 2d array, returning an iterator over its elements in row
+    /// major order. The tuple is set up as (row, column, value)
+    pub fn into_iter_row_major(self) -> IntoIterRowMajor<T> {
+        IntoIterRowMajor::new(self.data, self.width)
     }
 
     /// Returns an iterator that will iterate over the 2d array in column major
     /// order. The tuple is set up as (row, column, value)
-    pub fn iter_col_major(&self) -> impl Iterator<Item = (usize, usize, &T)> {
-        let mut col_iter_vec: Vec<_> = vec![];
-        for col_idx in 0..self.width {
-            let column_vec: Vec<_> = self
-                .array_2d
-                .iter()
-                .enumerate()
-                .map(|(row_idx, row_vec)| (row_idx, col_idx, row_vec.get(col_idx).unwrap()))
-                .collect();
-            col_iter_vec.push(column_vec);
-        }
+    pub fn iter_col_major(&self) -> IterColMajor<'_, T> {
         // Any Array2 must be able to be iterated over by column major
-        col_iter_vec
-            .into_iter()
-            .flat_map(|inner_vec| inner_vec.into_iter())
+        IterColMajor::new(&self.data, self.width, self.height)
+    }
+
+    /// Returns an mutable iterator that will iterate over the 2d array in column major
+    /// order. The tuple is set up as (row, column, value)
+    pub fn iter_col_major_mut(&mut self) -> IterColMajorMut<'_, T> {
+        IterColMajorMut::new(&mut self.data, self.width, self.height)
+    }
+
+    /// Consumes the 2d array, returning an iterator over its elements in column
+    /// major order. The tuple is set up as (row, column, value)
+    pub fn into_iter_col_major(self) -> IntoIterColMajor<T> {
+        IntoIterColMajor::new(self.data, self.width, self.height)
+    }
+
+    /// Returns the elements of the 2d array as a slice in row major order
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Consumes the 2d array, returning its elements as a vector in row major order
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    // Returns the position in the flat vector of the element at the given row and column,
+    // or None if either index is out of range
+    fn flat_index(&self, row_idx: usize, column_idx: usize) -> Option<usize> {
+        if row_idx < self.height && column_idx < self.width {
+            Some(row_idx * self.width + column_idx)
+        } else {
+            None
+        }
     }
 
     /// Returns a reference to an element from the 2d array given the column and
-    /// row position
+    /// row position, or None if either index is out of range
     ///
     /// # Arguments
     ///
@@ -151,18 +328,18 @@ impl<T: Clone> Array2<T> {
     pub fn get(&self, row_idx: usize, column_idx: usize) -> Option<&T> {
         // Any element in the Array2 should have two indices corresponding to the respective column
         // and row position, which is width and height.
-        self.array_2d[row_idx].get(column_idx)
+        self.flat_index(row_idx, column_idx).map(|index| &self.data[index])
     }
 
     /// Returns a mutable reference to an element from the 2d array given the column and
-    /// row position
+    /// row position, or None if either index is out of range
     ///
     /// # Arguments
     ///
     /// * 'row_idx': a usize stating the chosen row index
     /// * 'column_idx': a usize stating the chosen column index
     pub fn get_mut(&mut self, row_idx: usize, column_idx: usize) -> Option<&mut T> {
-        self.array_2d[row_idx].get_mut(column_idx)
+        self.flat_index(row_idx, column_idx).map(|index| &mut self.data[index])
     }
 
     /// Updates a single cell in the 2d array.
@@ -172,7 +349,227 @@ impl<T: Clone> Array2<T> {
     /// * 'row_idx': a usize stating the chosen row index
     /// * 'column_idx': a usize stating the chosen column index
     /// * 'value': the new value to be inserted into the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of range
     pub fn insert(&mut self, row_idx: usize, column_idx: usize, value: T) {
-        self.array_2d[row_idx][column_idx] = value
+        self[(row_idx, column_idx)] = value
+    }
+
+    /// Updates a single cell in the 2d array, handing `value` back as an error
+    /// if either index is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    /// * 'column_idx': a usize stating the chosen column index
+    /// * 'value': the new value to be inserted into the 2d array
+    pub fn try_insert(&mut self, row_idx: usize, column_idx: usize, value: T) -> Result<(), T> {
+        match self.get_mut(row_idx, column_idx) {
+            Some(element) => {
+                *element = value;
+                Ok(())
+            }
+            None => Err(value),
+        }
+    }
+
+    /// Replaces a single cell in the 2d array, returning the value that was
+    /// there before, or None if either index is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    /// * 'column_idx': a usize stating the chosen column index
+    /// * 'value': the new value to be placed into the 2d array
+    pub fn set(&mut self, row_idx: usize, column_idx: usize, value: T) -> Option<T> {
+        self.get_mut(row_idx, column_idx)
+            .map(|element| std::mem::replace(element, value))
+    }
+}
+
+/// Indexes the 2d array with a (row, column) tuple
+impl<T: Clone> Index<(usize, usize)> for Array2<T> {
+    type Output = T;
+
+    fn index(&self, (row_idx, column_idx): (usize, usize)) -> &T {
+        self.get(row_idx, column_idx).unwrap_or_else(|| {
+            panic!("index ({}, {}) out of bounds for a {}x{} array", row_idx, column_idx, self.width, self.height)
+        })
+    }
+}
+
+/// Mutably indexes the 2d array with a (row, column) tuple
+impl<T: Clone> IndexMut<(usize, usize)> for Array2<T> {
+    fn index_mut(&mut self, (row_idx, column_idx): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(row_idx, column_idx).unwrap_or_else(|| {
+            panic!("index ({}, {}) out of bounds for a {}x{} array", row_idx, column_idx, width, height)
+        })
+    }
+}
+
+/// Iterates over the 2d array in row major order, the same as `iter_row_major`
+impl<'a, T: Clone> IntoIterator for &'a Array2<T> {
+    type Item = (usize, usize, &'a T);
+    type IntoIter = IterRowMajor<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_row_major()
+    }
+}
+
+/// Iterates over the 2d array in row major order, the same as `iter_row_major_mut`
+impl<'a, T: Clone> IntoIterator for &'a mut Array2<T> {
+    type Item = (usize, usize, &'a mut T);
+    type IntoIter = IterRowMajorMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_row_major_mut()
+    }
+}
+
+/// Consumes the 2d array in row major order, the same as `into_iter_row_major`
+impl<T: Clone> IntoIterator for Array2<T> {
+    type Item = (usize, usize, T);
+    type IntoIter = IntoIterRowMajor<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_iter_row_major()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{grid, SHAPES};
+    use crate::{Array2, Array2Error};
+
+    // Checks that an array has the given size and holds the given elements in row major order
+    fn assert_array<T: Clone + PartialEq + std::fmt::Debug>(array: &Array2<T>, width: usize, height: usize, elements: &[T]) {
+        assert_eq!((array.width(), array.height()), (width, height));
+        assert_eq!(array.as_slice(), elements);
+    }
+
+    #[test]
+    fn fallible_constructors_read_both_orders() {
+        for (width, height) in SHAPES {
+            let expected = grid(width, height);
+            let row_major = Array2::try_from_row_major(expected.as_slice().to_vec(), width, height).unwrap();
+            assert_array(&row_major, width, height, expected.as_slice());
+            let col_major: Vec<usize> = expected.iter_col_major().map(|(_, _, val)| *val).collect();
+            let col_major = Array2::try_from_col_major(col_major, width, height).unwrap();
+            assert_array(&col_major, width, height, expected.as_slice());
+        }
+    }
+
+    #[test]
+    fn fallible_constructors_report_a_length_mismatch() {
+        let error = Array2Error::LengthMismatch { width: 2, height: 3, expected: 6, actual: 5 };
+        assert_eq!(Array2::try_from_row_major(vec![0; 5], 2, 3).err(), Some(error));
+        assert_eq!(Array2::try_from_col_major(vec![0; 5], 2, 3).err(), Some(error));
+        assert_eq!(error.to_string(), "a 2x3 array needs 6 elements, but 5 were given");
+        let error = Array2Error::WidthExceedsSource { width: 4, source_width: 3 };
+        assert_eq!(error.to_string(), "an array 4 columns wide cannot be taken from rows of 3 elements");
+    }
+
+    #[test]
+    #[should_panic(expected = "a 2x3 array needs 6 elements, but 7 were given")]
+    fn from_row_major_panics_on_a_length_mismatch() {
+        Array2::from_row_major(vec![0; 7], 2, 3);
+    }
+
+    #[test]
+    fn truncating_keeps_the_top_left_corner() {
+        // an odd-width source loses its last column, and the rows past the height are dropped
+        let truncated = Array2::from_row_major_truncating(grid(5, 4).into_vec(), 5, 4, 3);
+        assert_array(&truncated, 4, 3, grid(4, 3).as_slice());
+        let untouched = Array2::from_row_major_truncating(grid(3, 3).into_vec(), 3, 3, 2);
+        assert_array(&untouched, 3, 2, grid(3, 2).as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "an array 4 columns wide cannot be taken from rows of 3 elements")]
+    fn truncating_panics_when_wider_than_the_source() {
+        Array2::from_row_major_truncating(grid(3, 2).into_vec(), 3, 4, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "a 3x2 array needs 6 elements, but 5 were given")]
+    fn truncating_panics_on_too_few_rows() {
+        Array2::from_row_major_truncating(vec![0; 5], 3, 2, 2);
+    }
+
+    #[test]
+    fn access_out_of_range_returns_none() {
+        let mut array = grid(3, 2);
+        assert_eq!(array.get(1, 2), Some(&12));
+        for (row_idx, column_idx) in [(2, 0), (0, 3), (2, 3)] {
+            assert_eq!(array.get(row_idx, column_idx), None);
+            assert_eq!(array.get_mut(row_idx, column_idx), None);
+        }
+        *array.get_mut(1, 2).unwrap() = 99;
+        assert_eq!(array[(1, 2)], 99);
+    }
+
+    #[test]
+    fn try_insert_and_set_hand_values_back() {
+        let mut array = grid(3, 2);
+        assert_eq!(array.try_insert(0, 1, 50), Ok(()));
+        assert_eq!(array.try_insert(2, 0, 60), Err(60));
+        assert_eq!(array.try_insert(0, 3, 70), Err(70));
+        assert_eq!(array.set(0, 1, 80), Some(50));
+        assert_eq!(array.set(0, 3, 90), None);
+        assert_array(&array, 3, 2, &[0, 80, 2, 10, 11, 12]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index (2, 1) out of bounds for a 3x2 array")]
+    fn indexing_panics_out_of_range() {
+        let _ = grid(3, 2)[(2, 1)];
+    }
+
+    #[test]
+    #[should_panic(expected = "index (0, 3) out of bounds for a 3x2 array")]
+    fn mutable_indexing_panics_out_of_range() {
+        grid(3, 2)[(0, 3)] = 0;
+    }
+
+    #[test]
+    fn from_fn_and_map_keep_every_position() {
+        for (width, height) in SHAPES {
+            let array = Array2::from_fn(width, height, |row_idx, column_idx| (row_idx, column_idx));
+            let positions: Vec<(usize, usize)> = array.iter_row_major().map(|(r, c, _)| (r, c)).collect();
+            assert_array(&array, width, height, &positions);
+            let doubled: Vec<usize> = grid(width, height).as_slice().iter().map(|val| val * 2).collect();
+            assert_array(&grid(width, height).map(|val| val * 2), width, height, &doubled);
+            let indexed = grid(width, height).map_with_index(|row_idx, column_idx, val| (row_idx, column_idx, *val));
+            let expected: Vec<(usize, usize, usize)> = positions.iter().map(|&(r, c)| (r, c, r * 10 + c)).collect();
+            assert_array(&indexed, width, height, &expected);
+        }
+    }
+
+    #[test]
+    fn zip_with_pairs_elements_at_the_same_position() {
+        let labels = Array2::from_fn(3, 2, |row_idx, column_idx| format!("{}{}", row_idx, column_idx));
+        let zipped = grid(3, 2).zip_with(&labels, |val, label| format!("{}={}", label, val));
+        assert_array(&zipped, 3, 2, &["00=0", "01=1", "02=2", "10=10", "11=11", "12=12"].map(String::from));
+        for (width, height) in SHAPES {
+            let sums = grid(width, height).zip_with(&grid(width, height), |a, b| a + b);
+            assert_array(&sums, width, height, grid(width, height).map(|val| val * 2).as_slice());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot zip a 3x2 array with a 2x3 array")]
+    fn zip_with_panics_on_a_size_mismatch() {
+        grid(3, 2).zip_with(&grid(2, 3), |a, b| a + b);
+    }
+
+    #[test]
+    fn fold_visits_elements_in_row_major_order() {
+        assert_eq!(grid(3, 2).fold(Vec::new(), |mut seen, val| { seen.push(*val); seen }), vec![0, 1, 2, 10, 11, 12]);
+        assert_eq!(grid(3, 2).fold(0, |sum, val| sum + val), 36);
+        assert_eq!(grid(0, 3).fold(7, |sum, val| sum + val), 7);
     }
 }
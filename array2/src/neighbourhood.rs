@@ -0,0 +1,196 @@
+//! Neighbourhoods around the elements of an 'Array2<T>', for filters that
+//! look at the elements surrounding each one, such as convolution
+
+use crate::geometry::EdgeMode;
+use crate::Array2;
+use std::ops::{Add, Index, Mul};
+
+/// The neighbourhood of an element of a 2d array, borrowed from it. The element
+/// sits in the middle of the window, at row `height / 2` and column `width / 2`,
+/// and any position past the edge of the array is read as the edge mode asks.
+pub struct Window<'a, T: Clone> {
+    array: &'a Array2<T>,
+    mode: &'a EdgeMode<T>,
+    // the row and column of the array at the top left of the window, which may lie
+    // past the edge
+    top: isize,
+    left: isize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T: Clone> Window<'a, T> {
+    /// Returns the width of the window, also referred to as the amount of 'columns'
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the window, also referred to as the amount of 'rows'
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns a reference to an element of the window given its row and column, or
+    /// None if either index is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    /// * 'column_idx': a usize stating the chosen column index
+    pub fn get(&self, row_idx: usize, column_idx: usize) -> Option<&'a T> {
+        if row_idx >= self.height || column_idx >= self.width {
+            return None;
+        }
+        let source_row = self.mode.source_index(self.top + row_idx as isize, self.array.height);
+        let source_col = self.mode.source_index(self.left + column_idx as isize, self.array.width);
+        match (source_row, source_col, self.mode) {
+            (Some(source_row), Some(source_col), _) => self.array.get(source_row, source_col),
+            (_, _, EdgeMode::Constant(value)) => Some(value),
+            _ => unreachable!("only constant edges leave a position without a source"),
+        }
+    }
+
+    /// Returns an iterator that will iterate over the window in row major
+    /// order. The tuple is set up as (row, column, value)
+    pub fn iter_row_major(&self) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        let window = *self;
+        (0..self.height).flat_map(move |row_idx| {
+            (0..window.width).map(move |column_idx| (row_idx, column_idx, window.get(row_idx, column_idx).unwrap()))
+        })
+    }
+}
+
+impl<T: Clone> Clone for Window<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone> Copy for Window<'_, T> {}
+
+impl<T: Clone> Index<(usize, usize)> for Window<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row_idx, column_idx): (usize, usize)) -> &T {
+        self.get(row_idx, column_idx).unwrap_or_else(|| {
+            panic!("index ({}, {}) out of bounds for a {}x{} window", row_idx, column_idx, self.width, self.height)
+        })
+    }
+}
+
+impl<T: Clone> Array2<T> {
+    /// Returns the `window_width` x `window_height` neighbourhood around a single
+    /// element of the 2d array
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the row of the element in the middle
+    /// * 'column_idx': a usize stating the column of the element in the middle
+    /// * 'window_width': a usize containing the amount of columns in the window
+    /// * 'window_height': a usize containing the amount of rows in the window
+    /// * 'mode': how to read the positions that lie past the edge of the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of range
+    pub fn neighbourhood<'a>(&'a self, row_idx: usize, column_idx: usize, window_width: usize, window_height: usize, mode: &'a EdgeMode<T>) -> Window<'a, T> {
+        assert!(row_idx < self.height && column_idx < self.width,
+            "index ({}, {}) out of bounds for a {}x{} array", row_idx, column_idx, self.width, self.height);
+        Window {
+            array: self,
+            mode,
+            top: row_idx as isize - (window_height / 2) as isize,
+            left: column_idx as isize - (window_width / 2) as isize,
+            width: window_width,
+            height: window_height,
+        }
+    }
+
+    /// Returns an iterator over the neighbourhood of every element of the 2d array
+    /// in row major order. The tuple is set up as (row, column, window), where the
+    /// element at (row, column) is in the middle of the window.
+    ///
+    /// # Arguments
+    ///
+    /// * 'window_width': a usize containing the amount of columns in each window
+    /// * 'window_height': a usize containing the amount of rows in each window
+    /// * 'mode': how to read the positions that lie past the edge of the 2d array,
+    ///   the same as the padding added by `pad`
+    pub fn windows<'a>(&'a self, window_width: usize, window_height: usize, mode: &'a EdgeMode<T>) -> impl Iterator<Item = (usize, usize, Window<'a, T>)> {
+        (0..self.height).flat_map(move |row_idx| {
+            (0..self.width).map(move |column_idx| {
+                (row_idx, column_idx, self.neighbourhood(row_idx, column_idx, window_width, window_height, mode))
+            })
+        })
+    }
+
+    /// Returns the convolution of the 2d array with `kernel`. Each element becomes the
+    /// sum of its neighbourhood weighted by the kernel turned a half turn, the same as
+    /// sliding the mirrored kernel over the array; a symmetric kernel is used as is.
+    ///
+    /// # Arguments
+    ///
+    /// * 'kernel': the weights, centred at row `height / 2` and column `width / 2`
+    /// * 'mode': how to read the positions that lie past the edge of the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension of the kernel is even, as it then has no centre element
+    pub fn convolve(&self, kernel: &Array2<T>, mode: &EdgeMode<T>) -> Array2<T>
+    where
+        T: Default + Add<Output = T> + Mul<Output = T>,
+    {
+        let (kernel_width, kernel_height) = (kernel.width, kernel.height);
+        assert!(kernel_width % 2 == 1 && kernel_height % 2 == 1,
+            "a {}x{} kernel has no centre element", kernel_width, kernel_height);
+        let data = self
+            .windows(kernel_width, kernel_height, mode)
+            .map(|(_r, _c, window)| {
+                window.iter_row_major().fold(T::default(), |total, (row_idx, column_idx, val)| {
+                    let weight = kernel[(kernel_height - 1 - row_idx, kernel_width - 1 - column_idx)].clone();
+                    total + weight * val.clone()
+                })
+            })
+            .collect();
+        Array2::from_row_major(data, self.width, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convolve_turns_the_kernel_a_half_turn() {
+        let array = Array2::from_row_major(vec![1, 2, 3, 4, 5, 6, 7, 8, 9], 3, 3);
+        // each element becomes 1 * right + 2 * itself + 3 * left
+        let kernel = Array2::from_row_major(vec![1, 2, 3], 3, 1);
+        assert_eq!(array.convolve(&kernel, &EdgeMode::Constant(0)).as_slice(), &[4, 10, 12, 13, 28, 27, 22, 46, 42]);
+        // and the same down the columns
+        let kernel = Array2::from_row_major(vec![1, 2, 3], 1, 3);
+        assert_eq!(array.convolve(&kernel, &EdgeMode::Constant(0)).as_slice(), &[6, 9, 12, 18, 24, 30, 26, 31, 36]);
+    }
+
+    #[test]
+    fn convolve_reads_past_the_edge_as_asked() {
+        let array = Array2::from_row_major(vec![1, 2, 3, 4], 2, 2);
+        let boxed = Array2::from_row_major(vec![1; 9], 3, 3);
+        assert_eq!(array.convolve(&boxed, &EdgeMode::Replicate).as_slice(), &[18, 21, 24, 27]);
+        assert_eq!(array.convolve(&boxed, &EdgeMode::Constant(0)).as_slice(), &[10, 10, 10, 10]);
+        assert_eq!(array.convolve(&boxed, &EdgeMode::Reflect).as_slice(), &[27, 24, 21, 18]);
+    }
+
+    #[test]
+    fn convolve_with_the_unit_kernel_is_a_copy() {
+        let array = Array2::from_fn(4, 3, |row_idx, column_idx| (row_idx * 10 + column_idx) as i32);
+        let unit = Array2::from_row_major(vec![1], 1, 1);
+        assert_eq!(array.convolve(&unit, &EdgeMode::Replicate).as_slice(), array.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "a 2x3 kernel has no centre element")]
+    fn convolve_with_an_even_kernel_panics() {
+        let array = Array2::from_row_major(vec![1, 2, 3, 4], 2, 2);
+        array.convolve(&Array2::from_row_major(vec![1; 6], 2, 3), &EdgeMode::Replicate);
+    }
+}
@@ -0,0 +1,109 @@
+//! Parallel iteration over an 'Array2<T>' with rayon, enabled by the
+//! `rayon` feature. Collecting any of these iterators gives the elements in
+//! the same order as the sequential iterators.
+
+use crate::Array2;
+use rayon::prelude::*;
+
+impl<T: Clone> Array2<T> {
+    /// Returns a parallel iterator that will iterate over the 2d array in row
+    /// major order. The tuple is set up as (row, column, value)
+    pub fn par_iter_row_major(&self) -> impl IndexedParallelIterator<Item = (usize, usize, &T)>
+    where
+        T: Sync,
+    {
+        let width = self.width;
+        self.data
+            .par_iter()
+            .enumerate()
+            .map(move |(index, val)| (index / width, index % width, val))
+    }
+
+    /// Returns a parallel iterator over the rows of the 2d array as mutable
+    /// slices, from top to bottom. The tuple is set up as (row, values)
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = (usize, &mut [T])>
+    where
+        T: Send,
+    {
+        // an array with no columns still has `height` empty rows
+        let width = self.width.max(1);
+        let empty_rows = if self.width == 0 { self.height } else { 0 };
+        self.data
+            .par_chunks_mut(width)
+            .map(|row| &mut row[..])
+            .chain((0..empty_rows).into_par_iter().map(|_| &mut [][..]))
+            .enumerate()
+    }
+
+    /// Returns a new 2d array of the same size holding `f` applied to every
+    /// element, with the elements split across threads
+    ///
+    /// # Arguments
+    ///
+    /// * 'f': a function from an element to its new value
+    pub fn par_map<U: Clone + Send>(&self, f: impl Fn(&T) -> U + Sync + Send) -> Array2<U>
+    where
+        T: Sync,
+    {
+        Array2 {
+            data: self.data.par_iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{grid, SHAPES};
+
+    #[test]
+    fn par_iter_row_major_matches_iter_row_major() {
+        for (width, height) in SHAPES.into_iter().chain([(97, 89)]) {
+            let array = grid(width, height);
+            let sequential: Vec<_> = array.iter_row_major().collect();
+            let parallel: Vec<_> = array.par_iter_row_major().collect();
+            assert_eq!(parallel, sequential);
+            assert_eq!(array.par_iter_row_major().len(), width * height);
+        }
+    }
+
+    #[test]
+    fn par_map_matches_map() {
+        for (width, height) in SHAPES.into_iter().chain([(97, 89)]) {
+            let array = grid(width, height);
+            let sequential = array.map(|val| val * 3 + 1);
+            let parallel = array.par_map(|val| val * 3 + 1);
+            assert_eq!((parallel.width(), parallel.height()), (width, height));
+            assert_eq!(parallel.as_slice(), sequential.as_slice());
+        }
+    }
+
+    #[test]
+    fn par_rows_mut_yields_every_row_in_order() {
+        for (width, height) in SHAPES.into_iter().chain([(97, 89)]) {
+            let mut array = grid(width, height);
+            let rows: Vec<(usize, Vec<usize>)> = array.par_rows_mut().map(|(row_idx, row)| (row_idx, row.to_vec())).collect();
+            let expected: Vec<(usize, Vec<usize>)> = (0..height)
+                .map(|row_idx| (row_idx, (0..width).map(|column_idx| array[(row_idx, column_idx)]).collect()))
+                .collect();
+            assert_eq!(rows, expected);
+        }
+    }
+
+    #[test]
+    fn par_rows_mut_writes_through() {
+        let mut array = grid(4, 3);
+        array.par_rows_mut().for_each(|(row_idx, row)| row.iter_mut().for_each(|val| *val += row_idx * 100));
+        assert_eq!(array.as_slice(), &[0, 1, 2, 3, 110, 111, 112, 113, 220, 221, 222, 223]);
+    }
+
+    #[test]
+    fn par_rows_mut_on_an_array_without_columns() {
+        let mut array = grid(0, 4);
+        let rows: Vec<(usize, usize)> = array.par_rows_mut().map(|(row_idx, row)| (row_idx, row.len())).collect();
+        assert_eq!(rows, vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+        assert_eq!(array.par_rows_mut().len(), 4);
+    }
+}
@@ -0,0 +1,12 @@
+//! Fixtures shared by the unit tests of every module
+
+use crate::Array2;
+
+/// The shapes every operation is checked on, as (width, height), including
+/// arrays with no rows, no columns, or neither
+pub(crate) const SHAPES: [(usize, usize); 7] = [(2, 3), (3, 2), (1, 4), (4, 1), (0, 3), (3, 0), (0, 0)];
+
+/// Returns an array whose elements record their own position, as row * 10 + column
+pub(crate) fn grid(width: usize, height: usize) -> Array2<usize> {
+    Array2::from_fn(width, height, |row_idx, column_idx| row_idx * 10 + column_idx)
+}
@@ -0,0 +1,270 @@
+//! Iteration over an 'Array2<T>' in fixed size rectangular tiles, such as
+//! the 2x2 blocks of an image codec
+
+use crate::view::{View, ViewMut};
+use crate::Array2;
+use std::ops::Index;
+
+/// What to do with the tiles along the right and bottom edges of a 2d array
+/// whose size is not a multiple of the tile size
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EdgePolicy {
+    /// Leave out any tile that would not be full size
+    Skip,
+    /// Make every tile full size, repeating the last row and column of the
+    /// 2d array into the part that lies past its edge
+    Pad,
+    /// Cut the edge tiles down to the part that lies inside the 2d array
+    Partial,
+}
+
+/// A tile of a 2d array, borrowed from it. A padded tile is larger than the
+/// part of the array it covers, and reads the nearest element of that part
+/// for any position past the edge of the array.
+pub struct Tile<'a, T> {
+    view: View<'a, T>,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> Tile<'a, T> {
+    /// Returns the width of the tile, including any padding
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the tile, including any padding
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the part of the 2d array the tile covers, without any padding
+    pub fn view(&self) -> View<'a, T> {
+        self.view
+    }
+
+    /// Returns a reference to an element of the tile given its row and column, or None
+    /// if either index is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    /// * 'column_idx': a usize stating the chosen column index
+    pub fn get(&self, row_idx: usize, column_idx: usize) -> Option<&'a T> {
+        if row_idx < self.height && column_idx < self.width {
+            // padding repeats the last row and column that lie inside the array
+            self.view.get(row_idx.min(self.view.height() - 1), column_idx.min(self.view.width() - 1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator that will iterate over the tile in row major
+    /// order. The tuple is set up as (row, column, value)
+    pub fn iter_row_major(&self) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        let tile = *self;
+        (0..self.height).flat_map(move |row_idx| {
+            (0..tile.width).map(move |column_idx| (row_idx, column_idx, tile.get(row_idx, column_idx).unwrap()))
+        })
+    }
+}
+
+impl<T> Clone for Tile<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Tile<'_, T> {}
+
+impl<T> Index<(usize, usize)> for Tile<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row_idx, column_idx): (usize, usize)) -> &T {
+        self.get(row_idx, column_idx).unwrap_or_else(|| {
+            panic!("index ({}, {}) out of bounds for a {}x{} tile", row_idx, column_idx, self.width, self.height)
+        })
+    }
+}
+
+// Returns how many tiles of `size` fit along a side of `length` elements
+fn tile_count(length: usize, size: usize, policy: EdgePolicy) -> usize {
+    match policy {
+        EdgePolicy::Skip => length / size,
+        EdgePolicy::Pad | EdgePolicy::Partial => length.div_ceil(size),
+    }
+}
+
+impl<T: Clone> Array2<T> {
+    /// Returns an iterator over the 2d array split into tiles of `tile_width` x
+    /// `tile_height` elements, in row major order of the tiles. The tuple is set up as
+    /// (tile row, tile column, tile), so the top left element of a tile sits at row
+    /// `tile row * tile_height` and column `tile column * tile_width` of the 2d array.
+    ///
+    /// # Arguments
+    ///
+    /// * 'tile_width': a usize containing the amount of columns in each tile
+    /// * 'tile_height': a usize containing the amount of rows in each tile
+    /// * 'policy': what to do with tiles that would lie past the edge of the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if either tile dimension is zero
+    pub fn blocks(&self, tile_width: usize, tile_height: usize, policy: EdgePolicy) -> impl Iterator<Item = (usize, usize, Tile<'_, T>)> {
+        assert!(tile_width > 0 && tile_height > 0, "tiles must not be empty");
+        let columns = tile_count(self.width, tile_width, policy);
+        (0..tile_count(self.height, tile_height, policy)).flat_map(move |tile_row| {
+            (0..columns).map(move |tile_col| {
+                let (row, col) = (tile_row * tile_height, tile_col * tile_width);
+                let view = self.view(row, col, tile_height.min(self.height - row), tile_width.min(self.width - col));
+                let (width, height) = match policy {
+                    EdgePolicy::Pad => (tile_width, tile_height),
+                    EdgePolicy::Skip | EdgePolicy::Partial => (view.width(), view.height()),
+                };
+                (tile_row, tile_col, Tile { view, width, height })
+            })
+        })
+    }
+
+    /// Returns an iterator over the 2d array split into tiles of `tile_width` x
+    /// `tile_height` elements that can be written to, in row major order of the tiles.
+    /// The tuple is set up as (tile row, tile column, tile).
+    ///
+    /// # Arguments
+    ///
+    /// * 'tile_width': a usize containing the amount of columns in each tile
+    /// * 'tile_height': a usize containing the amount of rows in each tile
+    /// * 'policy': what to do with tiles that would lie past the edge of the 2d array
+    ///
+    /// # Panics
+    ///
+    /// Panics if either tile dimension is zero, or if `policy` is `EdgePolicy::Pad`,
+    /// as padding has nowhere to be written
+    pub fn blocks_mut(&mut self, tile_width: usize, tile_height: usize, policy: EdgePolicy) -> impl Iterator<Item = (usize, usize, ViewMut<'_, T>)> {
+        assert!(tile_width > 0 && tile_height > 0, "tiles must not be empty");
+        assert!(policy != EdgePolicy::Pad, "padded tiles cannot be written to");
+        let (width, height) = (self.width, self.height);
+        let columns = tile_count(width, tile_width, policy);
+        let data = self.data.as_mut_ptr();
+        (0..tile_count(height, tile_height, policy)).flat_map(move |tile_row| {
+            (0..columns).map(move |tile_col| {
+                let (row, col) = (tile_row * tile_height, tile_col * tile_width);
+                // SAFETY: every tile starts inside the array and is cut down to fit in it, no
+                // two tiles share an element, and the array stays mutably borrowed for as
+                // long as any tile does
+                let view = unsafe {
+                    ViewMut::from_raw_parts(data.add(row * width + col), width,
+                        tile_height.min(height - row), tile_width.min(width - col))
+                };
+                (tile_row, tile_col, view)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::grid;
+
+
+    // The position, size and elements of every tile, in the order they are yielded
+    type Layout = Vec<(usize, usize, usize, usize, Vec<usize>)>;
+
+    fn layout_mut(array: &mut Array2<usize>, tile_width: usize, tile_height: usize, policy: EdgePolicy) -> Layout {
+        array.blocks_mut(tile_width, tile_height, policy)
+            .map(|(tile_row, tile_col, view)| (tile_row, tile_col, view.width(), view.height(), view.to_array2().as_slice().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn blocks_mut_splits_the_array_into_tiles() {
+        let mut array = grid(5, 3);
+        assert_eq!(layout_mut(&mut array, 2, 2, EdgePolicy::Skip), vec![
+            (0, 0, 2, 2, vec![0, 1, 10, 11]),
+            (0, 1, 2, 2, vec![2, 3, 12, 13]),
+        ]);
+        assert_eq!(layout_mut(&mut array, 2, 2, EdgePolicy::Partial), vec![
+            (0, 0, 2, 2, vec![0, 1, 10, 11]),
+            (0, 1, 2, 2, vec![2, 3, 12, 13]),
+            (0, 2, 1, 2, vec![4, 14]),
+            (1, 0, 2, 1, vec![20, 21]),
+            (1, 1, 2, 1, vec![22, 23]),
+            (1, 2, 1, 1, vec![24]),
+        ]);
+    }
+
+    #[test]
+    fn blocks_mut_covers_the_same_elements_as_blocks() {
+        for (width, height) in [(5, 3), (4, 4), (1, 3), (3, 1), (0, 3), (3, 0), (0, 0)] {
+            for (tile_width, tile_height) in [(1, 1), (2, 2), (3, 2), (6, 6)] {
+                for policy in [EdgePolicy::Skip, EdgePolicy::Partial] {
+                    let mut array = grid(width, height);
+                    let expected: Layout = array.blocks(tile_width, tile_height, policy)
+                        .map(|(tile_row, tile_col, tile)| {
+                            let view = tile.view();
+                            (tile_row, tile_col, view.width(), view.height(), view.to_array2().as_slice().to_vec())
+                        })
+                        .collect();
+                    assert_eq!(layout_mut(&mut array, tile_width, tile_height, policy), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blocks_mut_writes_every_element_once() {
+        let mut array = grid(5, 3);
+        for (tile_row, tile_col, mut view) in array.blocks_mut(2, 2, EdgePolicy::Partial) {
+            for (_, _, val) in view.iter_row_major_mut() {
+                *val += 100 * (tile_row * 3 + tile_col + 1);
+            }
+        }
+        assert_eq!(array.as_slice(), &[
+            100, 101, 202, 203, 304,
+            110, 111, 212, 213, 314,
+            420, 421, 522, 523, 624,
+        ]);
+    }
+
+    #[test]
+    fn blocks_mut_tiles_can_be_held_together() {
+        let mut array = grid(5, 3);
+        let mut tiles: Vec<_> = array.blocks_mut(2, 2, EdgePolicy::Partial).collect();
+        // every tile borrows its own elements, so they can be written in any order
+        for (tile_row, tile_col, view) in tiles.iter_mut().rev() {
+            view.column_mut(0)[0] += 100 * (*tile_row * 3 + *tile_col + 1);
+        }
+        assert_eq!(array.as_slice(), &[
+            100, 1, 202, 3, 304,
+            10, 11, 12, 13, 14,
+            420, 21, 522, 23, 624,
+        ]);
+    }
+
+    #[test]
+    fn blocks_pads_with_the_nearest_element() {
+        let array = grid(3, 3);
+        let tiles: Vec<(usize, usize, Vec<usize>)> = array.blocks(2, 2, EdgePolicy::Pad)
+            .map(|(tile_row, tile_col, tile)| (tile_row, tile_col, tile.iter_row_major().map(|(_, _, val)| *val).collect()))
+            .collect();
+        assert_eq!(tiles, vec![
+            (0, 0, vec![0, 1, 10, 11]),
+            (0, 1, vec![2, 2, 12, 12]),
+            (1, 0, vec![20, 21, 20, 21]),
+            (1, 1, vec![22, 22, 22, 22]),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "tiles must not be empty")]
+    fn blocks_mut_with_empty_tiles_panics() {
+        grid(3, 3).blocks_mut(0, 2, EdgePolicy::Skip).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "padded tiles cannot be written to")]
+    fn blocks_mut_with_padding_panics() {
+        grid(3, 3).blocks_mut(2, 2, EdgePolicy::Pad).count();
+    }
+}
@@ -0,0 +1,650 @@
+//! Borrowed views into part of an 'Array2<T>' that read and write the
+//! elements in place, without copying them
+
+use crate::Array2;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+// Panics with the same message as the Array2 index operator when a view is indexed out of range
+fn out_of_bounds(row_idx: usize, column_idx: usize, width: usize, height: usize) -> ! {
+    panic!("index ({}, {}) out of bounds for a {}x{} view", row_idx, column_idx, width, height)
+}
+
+// Checks that the rectangle with its top left element at (row, col) fits inside a
+// width x height array
+fn check_rectangle(row: usize, col: usize, height: usize, width: usize, outer_width: usize, outer_height: usize) {
+    assert!(row + height <= outer_height && col + width <= outer_width,
+        "a {}x{} view at ({}, {}) lies outside the {}x{} array", width, height, row, col, outer_width, outer_height);
+}
+
+/// A single column of a 2d array, borrowed from it
+pub struct Column<'a, T> {
+    data: &'a [T],
+    stride: usize,
+    height: usize,
+}
+
+impl<'a, T> Column<'a, T> {
+    /// Returns the amount of elements in the column
+    pub fn len(&self) -> usize {
+        self.height
+    }
+
+    /// Returns true if the column has no elements
+    pub fn is_empty(&self) -> bool {
+        self.height == 0
+    }
+
+    /// Returns a reference to the element in the given row, or None if it is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    pub fn get(&self, row_idx: usize) -> Option<&'a T> {
+        if row_idx < self.height {
+            Some(&self.data[row_idx * self.stride])
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements of the column from top to bottom
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.data.iter().step_by(self.stride).take(self.height)
+    }
+}
+
+impl<T> Clone for Column<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Column<'_, T> {}
+
+impl<T> Index<usize> for Column<'_, T> {
+    type Output = T;
+
+    fn index(&self, row_idx: usize) -> &T {
+        self.get(row_idx).unwrap_or_else(|| out_of_bounds(row_idx, 0, 1, self.height))
+    }
+}
+
+/// A single column of a 2d array, mutably borrowed from it
+pub struct ColumnMut<'a, T> {
+    // points at the top element of the column, with elements `stride` apart
+    data: *mut T,
+    stride: usize,
+    height: usize,
+    _borrow: PhantomData<&'a mut T>,
+}
+
+// The column only hands out references to the elements it mutably borrows, so it can
+// cross threads whenever a mutable slice of them could
+unsafe impl<T: Send> Send for ColumnMut<'_, T> {}
+unsafe impl<T: Sync> Sync for ColumnMut<'_, T> {}
+
+impl<'a, T> ColumnMut<'a, T> {
+    /// Returns the amount of elements in the column
+    pub fn len(&self) -> usize {
+        self.height
+    }
+
+    /// Returns true if the column has no elements
+    pub fn is_empty(&self) -> bool {
+        self.height == 0
+    }
+
+    /// Returns a reference to the element in the given row, or None if it is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    pub fn get(&self, row_idx: usize) -> Option<&T> {
+        if row_idx < self.height {
+            // SAFETY: the row is in range, so the element lies inside the borrowed column
+            Some(unsafe { &*self.data.add(row_idx * self.stride) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element in the given row, or None if it is
+    /// out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    pub fn get_mut(&mut self, row_idx: usize) -> Option<&mut T> {
+        if row_idx < self.height {
+            // SAFETY: the row is in range, and the element stays borrowed through `self`
+            Some(unsafe { &mut *self.data.add(row_idx * self.stride) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements of the column from top to bottom
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |row_idx| &self[row_idx])
+    }
+
+    /// Returns a mutable iterator over the elements of the column from top to bottom
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> + use<'_, 'a, T> {
+        let (data, stride) = (self.data, self.stride);
+        // SAFETY: every row is in range and visited once, so the references never alias,
+        // and they stay borrowed through `self`
+        (0..self.height).map(move |row_idx| unsafe { &mut *data.add(row_idx * stride) })
+    }
+}
+
+impl<T> Index<usize> for ColumnMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, row_idx: usize) -> &T {
+        self.get(row_idx).unwrap_or_else(|| out_of_bounds(row_idx, 0, 1, self.len()))
+    }
+}
+
+impl<T> IndexMut<usize> for ColumnMut<'_, T> {
+    fn index_mut(&mut self, row_idx: usize) -> &mut T {
+        let height = self.len();
+        self.get_mut(row_idx).unwrap_or_else(|| out_of_bounds(row_idx, 0, 1, height))
+    }
+}
+
+/// A rectangle of a 2d array, borrowed from it. Rows and columns of the view are
+/// counted from its own top left element.
+pub struct View<'a, T> {
+    // starts at the top left element of the view, with rows `stride` elements apart
+    data: &'a [T],
+    stride: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> View<'a, T> {
+    // Borrows the rectangle with its top left element at (row, col) out of a row-major
+    // slice whose rows are `stride` elements long
+    pub(crate) fn new(data: &'a [T], stride: usize, row: usize, col: usize, height: usize, width: usize) -> Self {
+        // an empty view borrows nothing, as its corner may lie past the end of an empty array
+        if height == 0 || width == 0 {
+            return View { data: &data[..0], stride, width, height };
+        }
+        let start = row * stride + col;
+        View { data: &data[start..start + (height - 1) * stride + width], stride, width, height }
+    }
+
+    /// Returns the width of the view, also referred to as the amount of 'columns'
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the view, also referred to as the amount of 'rows'
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns a reference to an element of the view given its row and column, or None
+    /// if either index is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    /// * 'column_idx': a usize stating the chosen column index
+    pub fn get(&self, row_idx: usize, column_idx: usize) -> Option<&'a T> {
+        if row_idx < self.height && column_idx < self.width {
+            Some(&self.data[row_idx * self.stride + column_idx])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a single row of the view as a slice
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row_idx` is out of range
+    pub fn row(&self, row_idx: usize) -> &'a [T] {
+        assert!(row_idx < self.height, "row {} out of bounds for a view of height {}", row_idx, self.height);
+        &self.data[row_idx * self.stride..row_idx * self.stride + self.width]
+    }
+
+    /// Returns a single column of the view
+    ///
+    /// # Arguments
+    ///
+    /// * 'column_idx': a usize stating the chosen column index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `column_idx` is out of range
+    pub fn column(&self, column_idx: usize) -> Column<'a, T> {
+        assert!(column_idx < self.width, "column {} out of bounds for a view of width {}", column_idx, self.width);
+        // a view with no rows borrows nothing, so there is no element to start the column at
+        let data = if self.height == 0 { &self.data[..0] } else { &self.data[column_idx..] };
+        Column { data, stride: self.stride, height: self.height }
+    }
+
+    /// Returns a smaller view inside this one
+    ///
+    /// # Arguments
+    ///
+    /// * 'row': the row of the top left element of the new view
+    /// * 'col': the column of the top left element of the new view
+    /// * 'height': the amount of rows in the new view
+    /// * 'width': the amount of columns in the new view
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new view does not fit inside this one
+    pub fn view(&self, row: usize, col: usize, height: usize, width: usize) -> View<'a, T> {
+        check_rectangle(row, col, height, width, self.width, self.height);
+        View::new(self.data, self.stride, row, col, height, width)
+    }
+
+    /// Returns an iterator that will iterate over the view in row major
+    /// order. The tuple is set up as (row, column, value)
+    pub fn iter_row_major(&self) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        let view = *self;
+        (0..self.height).flat_map(move |row_idx| {
+            view.row(row_idx).iter().enumerate().map(move |(column_idx, val)| (row_idx, column_idx, val))
+        })
+    }
+}
+
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<T: Clone> View<'_, T> {
+    /// Copies the elements of the view into a new 2d array
+    pub fn to_array2(&self) -> Array2<T> {
+        let elements = self.iter_row_major().map(|(_r, _c, val)| val.clone()).collect();
+        Array2::from_row_major(elements, self.width, self.height)
+    }
+}
+
+impl<T> Index<(usize, usize)> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row_idx, column_idx): (usize, usize)) -> &T {
+        self.get(row_idx, column_idx)
+            .unwrap_or_else(|| out_of_bounds(row_idx, column_idx, self.width, self.height))
+    }
+}
+
+/// A rectangle of a 2d array, mutably borrowed from it. Rows and columns of the view
+/// are counted from its own top left element.
+pub struct ViewMut<'a, T> {
+    // points at the top left element of the view, with rows `stride` elements apart
+    data: *mut T,
+    stride: usize,
+    width: usize,
+    height: usize,
+    _borrow: PhantomData<&'a mut T>,
+}
+
+// The view only hands out references to the elements it mutably borrows, so it can
+// cross threads whenever a mutable slice of them could
+unsafe impl<T: Send> Send for ViewMut<'_, T> {}
+unsafe impl<T: Sync> Sync for ViewMut<'_, T> {}
+
+impl<'a, T> ViewMut<'a, T> {
+    // Mutably borrows the rectangle with its top left element at (row, col) out of a
+    // row-major slice whose rows are `stride` elements long
+    fn new(data: &'a mut [T], stride: usize, row: usize, col: usize, height: usize, width: usize) -> Self {
+        // an empty view borrows nothing, as its corner may lie past the end of an empty array
+        if height == 0 || width == 0 {
+            // SAFETY: a view with no elements never reads through its pointer
+            return unsafe { ViewMut::from_raw_parts(data.as_mut_ptr(), 0, height, width) };
+        }
+        let start = row * stride + col;
+        let data = &mut data[start..start + (height - 1) * stride + width];
+        // SAFETY: the slice holds every row of the view and is borrowed for 'a
+        unsafe { ViewMut::from_raw_parts(data.as_mut_ptr(), stride, height, width) }
+    }
+
+    // Mutably borrows the height x width rectangle whose top left element `data` points at,
+    // with rows `stride` elements apart.
+    //
+    // SAFETY: the caller must make sure every element of the rectangle is valid and stays
+    // borrowed by nothing else for 'a
+    pub(crate) unsafe fn from_raw_parts(data: *mut T, stride: usize, height: usize, width: usize) -> Self {
+        ViewMut { data, stride, width, height, _borrow: PhantomData }
+    }
+
+    /// Returns the width of the view, also referred to as the amount of 'columns'
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the view, also referred to as the amount of 'rows'
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns a reference to an element of the view given its row and column, or None
+    /// if either index is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    /// * 'column_idx': a usize stating the chosen column index
+    pub fn get(&self, row_idx: usize, column_idx: usize) -> Option<&T> {
+        if row_idx < self.height && column_idx < self.width {
+            Some(&self.row(row_idx)[column_idx])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to an element of the view given its row and column,
+    /// or None if either index is out of range
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    /// * 'column_idx': a usize stating the chosen column index
+    pub fn get_mut(&mut self, row_idx: usize, column_idx: usize) -> Option<&mut T> {
+        if row_idx < self.height && column_idx < self.width {
+            Some(&mut self.row_mut(row_idx)[column_idx])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a single row of the view as a slice
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row_idx` is out of range
+    pub fn row(&self, row_idx: usize) -> &[T] {
+        assert!(row_idx < self.height, "row {} out of bounds for a view of height {}", row_idx, self.height);
+        // SAFETY: the row is in range, so its `width` elements lie inside the view
+        unsafe { std::slice::from_raw_parts(self.data.add(row_idx * self.stride), self.width) }
+    }
+
+    /// Returns a single row of the view as a mutable slice
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row_idx` is out of range
+    pub fn row_mut(&mut self, row_idx: usize) -> &mut [T] {
+        assert!(row_idx < self.height, "row {} out of bounds for a view of height {}", row_idx, self.height);
+        // SAFETY: the row is in range, and its elements stay borrowed through `self`
+        unsafe { std::slice::from_raw_parts_mut(self.data.add(row_idx * self.stride), self.width) }
+    }
+
+    /// Returns a single column of the view that can be written to
+    ///
+    /// # Arguments
+    ///
+    /// * 'column_idx': a usize stating the chosen column index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `column_idx` is out of range
+    pub fn column_mut(&mut self, column_idx: usize) -> ColumnMut<'_, T> {
+        assert!(column_idx < self.width, "column {} out of bounds for a view of width {}", column_idx, self.width);
+        // a view with no rows has no element to start the column at
+        let data = if self.height == 0 {
+            self.data
+        } else {
+            // SAFETY: the column is in range, so its top element lies inside the view
+            unsafe { self.data.add(column_idx) }
+        };
+        ColumnMut { data, stride: self.stride, height: self.height, _borrow: PhantomData }
+    }
+
+    /// Returns an iterator that will iterate over the view in row major
+    /// order. The tuple is set up as (row, column, value)
+    pub fn iter_row_major(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        (0..self.height).flat_map(move |row_idx| {
+            self.row(row_idx).iter().enumerate().map(move |(column_idx, val)| (row_idx, column_idx, val))
+        })
+    }
+
+    /// Returns an mutable iterator that will iterate over the view in row major
+    /// order. The tuple is set up as (row, column, value)
+    pub fn iter_row_major_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut T)> + use<'_, 'a, T> {
+        let (data, stride, width) = (self.data, self.stride, self.width);
+        (0..self.height).flat_map(move |row_idx| {
+            // SAFETY: every row is in range and visited once, so the rows never alias, and
+            // they stay borrowed through `self`
+            let row = unsafe { std::slice::from_raw_parts_mut(data.add(row_idx * stride), width) };
+            row.iter_mut().enumerate().map(move |(column_idx, val)| (row_idx, column_idx, val))
+        })
+    }
+}
+
+impl<T: Clone> ViewMut<'_, T> {
+    /// Copies the elements of the view into a new 2d array
+    pub fn to_array2(&self) -> Array2<T> {
+        let elements = self.iter_row_major().map(|(_r, _c, val)| val.clone()).collect();
+        Array2::from_row_major(elements, self.width, self.height)
+    }
+}
+
+impl<T> Index<(usize, usize)> for ViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row_idx, column_idx): (usize, usize)) -> &T {
+        self.get(row_idx, column_idx)
+            .unwrap_or_else(|| out_of_bounds(row_idx, column_idx, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for ViewMut<'_, T> {
+    fn index_mut(&mut self, (row_idx, column_idx): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(row_idx, column_idx)
+            .unwrap_or_else(|| out_of_bounds(row_idx, column_idx, width, height))
+    }
+}
+
+impl<T: Clone> Array2<T> {
+    /// Returns a single row of the 2d array as a slice
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row_idx` is out of range
+    pub fn row(&self, row_idx: usize) -> &[T] {
+        assert!(row_idx < self.height, "row {} out of bounds for an array of height {}", row_idx, self.height);
+        &self.data[row_idx * self.width..(row_idx + 1) * self.width]
+    }
+
+    /// Returns a single row of the 2d array as a mutable slice
+    ///
+    /// # Arguments
+    ///
+    /// * 'row_idx': a usize stating the chosen row index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row_idx` is out of range
+    pub fn row_mut(&mut self, row_idx: usize) -> &mut [T] {
+        assert!(row_idx < self.height, "row {} out of bounds for an array of height {}", row_idx, self.height);
+        &mut self.data[row_idx * self.width..(row_idx + 1) * self.width]
+    }
+
+    /// Returns a single column of the 2d array
+    ///
+    /// # Arguments
+    ///
+    /// * 'column_idx': a usize stating the chosen column index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `column_idx` is out of range
+    pub fn column(&self, column_idx: usize) -> Column<'_, T> {
+        self.view(0, 0, self.height, self.width).column(column_idx)
+    }
+
+    /// Returns a single column of the 2d array that can be written to
+    ///
+    /// # Arguments
+    ///
+    /// * 'column_idx': a usize stating the chosen column index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `column_idx` is out of range
+    pub fn column_mut(&mut self, column_idx: usize) -> ColumnMut<'_, T> {
+        assert!(column_idx < self.width, "column {} out of bounds for an array of width {}", column_idx, self.width);
+        // an array with no rows has no element to start the column at
+        let data = if self.height == 0 { self.data.as_mut_ptr() } else { self.data[column_idx..].as_mut_ptr() };
+        ColumnMut { data, stride: self.width, height: self.height, _borrow: PhantomData }
+    }
+
+    /// Returns a view of a rectangle of the 2d array
+    ///
+    /// # Arguments
+    ///
+    /// * 'row': the row of the top left element of the view
+    /// * 'col': the column of the top left element of the view
+    /// * 'height': the amount of rows in the view
+    /// * 'width': the amount of columns in the view
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view does not fit inside the 2d array
+    pub fn view(&self, row: usize, col: usize, height: usize, width: usize) -> View<'_, T> {
+        check_rectangle(row, col, height, width, self.width, self.height);
+        View::new(&self.data, self.width, row, col, height, width)
+    }
+
+    /// Returns a view of a rectangle of the 2d array that can be written to
+    ///
+    /// # Arguments
+    ///
+    /// * 'row': the row of the top left element of the view
+    /// * 'col': the column of the top left element of the view
+    /// * 'height': the amount of rows in the view
+    /// * 'width': the amount of columns in the view
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view does not fit inside the 2d array
+    pub fn view_mut(&mut self, row: usize, col: usize, height: usize, width: usize) -> ViewMut<'_, T> {
+        check_rectangle(row, col, height, width, self.width, self.height);
+        ViewMut::new(&mut self.data, self.width, row, col, height, width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{grid, SHAPES};
+
+    #[test]
+    fn view_mut_reads_like_view() {
+        for (width, height) in SHAPES {
+            let mut array = grid(width, height);
+            for (row, col, view_height, view_width) in [(0, 0, height, width), (height / 2, width / 2, height - height / 2, width - width / 2)] {
+                let expected = array.view(row, col, view_height, view_width).to_array2();
+                let view = array.view_mut(row, col, view_height, view_width);
+                assert_eq!((view.width(), view.height()), (view_width, view_height));
+                assert_eq!(view.to_array2().as_slice(), expected.as_slice());
+                for (row_idx, column_idx, val) in expected.iter_row_major() {
+                    assert_eq!(view[(row_idx, column_idx)], *val);
+                }
+                assert!(view.get(view_height, 0).is_none() && view.get(0, view_width).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn view_mut_writes_into_the_array() {
+        let mut array = grid(4, 3);
+        let mut view = array.view_mut(1, 1, 2, 2);
+        view[(0, 0)] = 100;
+        *view.get_mut(1, 1).unwrap() = 200;
+        assert!(view.get_mut(2, 0).is_none());
+        view.row_mut(0)[1] = 300;
+        for (row_idx, column_idx, val) in view.iter_row_major_mut() {
+            *val += row_idx * 1000 + column_idx * 10000;
+        }
+        assert_eq!(view.row(1), &[1021, 11200]);
+        assert_eq!(array.as_slice(), &[0, 1, 2, 3, 10, 100, 10300, 13, 20, 1021, 11200, 23]);
+    }
+
+    #[test]
+    fn column_mut_walks_down_one_column() {
+        let mut array = grid(3, 4);
+        let mut column = array.column_mut(1);
+        assert_eq!((column.len(), column.is_empty()), (4, false));
+        assert_eq!(column.iter().copied().collect::<Vec<_>>(), vec![1, 11, 21, 31]);
+        column[0] = 5;
+        *column.get_mut(3).unwrap() = 6;
+        assert!(column.get(4).is_none() && column.get_mut(4).is_none());
+        column.iter_mut().for_each(|val| *val *= 2);
+        assert_eq!(array.as_slice(), &[0, 10, 2, 10, 22, 12, 20, 42, 22, 30, 12, 32]);
+
+        // a column of a view only reaches the rows of the view
+        let mut view = array.view_mut(1, 1, 2, 2);
+        let mut column = view.column_mut(1);
+        assert_eq!(column.iter().copied().collect::<Vec<_>>(), vec![12, 22]);
+        column[1] = 7;
+        assert_eq!(array.column(2).iter().copied().collect::<Vec<_>>(), vec![2, 12, 7, 32]);
+    }
+
+    #[test]
+    fn column_of_an_array_without_rows_is_empty() {
+        let array = grid(3, 0);
+        let column = array.column(1);
+        assert_eq!((column.len(), column.is_empty(), column.iter().count()), (0, true, 0));
+        assert!(column.get(0).is_none());
+        assert!(array.view(0, 1, 0, 2).column(1).is_empty());
+        let array = grid(3, 2);
+        assert!(array.view(1, 0, 0, 3).column(2).is_empty());
+        assert!(array.view(2, 0, 0, 3).column(2).is_empty());
+    }
+
+    #[test]
+    fn column_mut_of_an_array_without_rows_is_empty() {
+        let mut array = grid(3, 0);
+        let column = array.column_mut(2);
+        assert_eq!((column.len(), column.is_empty()), (0, true));
+        assert!(array.view_mut(0, 1, 0, 2).column_mut(1).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of bounds for an array of width 3")]
+    fn column_mut_past_the_edge_panics() {
+        grid(3, 2).column_mut(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "a 2x2 view at (1, 2) lies outside the 3x2 array")]
+    fn view_mut_outside_the_array_panics() {
+        grid(3, 2).view_mut(1, 2, 2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "index (2, 0) out of bounds for a 2x2 view")]
+    fn indexing_view_mut_out_of_range_panics() {
+        let mut array = grid(3, 3);
+        let mut view = array.view_mut(0, 0, 2, 2);
+        view[(2, 0)] = 1;
+    }
+}
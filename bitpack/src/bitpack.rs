@@ -128,13 +128,12 @@ pub fn news(word: u64, width: u64, lsb: u64, value: i64) -> Option<u64> {
     if width > 64 || width + lsb > 64 || !fitss(value, width) {
         None
     } else {
-        let val; 
-        if value < 0 {
+        let val = if value < 0 {
             // get the correct u64 respective to the width of the bit field
-            val = srl(shift_left(value as u64, 64-width), 64-width);
+            srl(shift_left(value as u64, 64-width), 64-width)
         } else {
-            val = value as u64;
-        }
+            value as u64
+        };
         let left = shift_left(srl(word, width+lsb), width+lsb);
         let right = srl(shift_left(word, 64-lsb), 64-lsb);
         Some(left | shift_left(val, lsb) | right)
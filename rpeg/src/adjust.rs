@@ -0,0 +1,112 @@
+use crate::quantization::BitValue;
+use array2::Array2;
+use csc411_arith::{chroma_of_index, index_of_chroma};
+
+// The largest value of the 9 bit unsigned `a`
+const A_MAX: i64 = 511;
+// The largest magnitude the encoder gives the 5 bit signed `b`, `c`, and `d`
+const BCD_MAX: i64 = 15;
+// The largest magnitude in the chroma quantization table
+const CHROMA_MAX: f32 = 0.35;
+// The magnitude of the two entries of the chroma quantization table closest to zero
+const GRAY_CHROMA: f32 = 0.011;
+
+// Clamps a value into [min, max], counting it in `saturated` if it had to be clamped
+fn saturate(value: i64, min: i64, max: i64, saturated: &mut usize) -> i64 {
+    if value < min || value > max {
+        *saturated += 1;
+    }
+    value.clamp(min, max)
+}
+
+/// Brightens or darkens the image without decoding it by shifting the average luminance
+/// `a` of every block. Returns the adjusted blocks along with the number of blocks whose
+/// `a` saturated at black or white.
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+/// * `brightness`: the amount of luminance to add, where 1.0 is the full range
+pub fn adjust_brightness(mut codewords: Array2<BitValue>, brightness: f64) -> (Array2<BitValue>, usize) {
+    let shift = (brightness * A_MAX as f64).round() as i64;
+    let mut saturated = 0;
+    for (_r, _c, block) in codewords.iter_row_major_mut() {
+        block.a = saturate(block.a as i64 + shift, 0, A_MAX, &mut saturated) as u64;
+    }
+    (codewords, saturated)
+}
+
+/// Changes the contrast of the image without decoding it by scaling the average luminance
+/// `a` of every block around middle gray, and scaling the gradients `b`, `c`, and `d`.
+/// Returns the adjusted blocks along with the number of coefficients that saturated.
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+/// * `contrast`: the factor to scale the contrast by, where 1.0 leaves the image unchanged
+pub fn adjust_contrast(mut codewords: Array2<BitValue>, contrast: f64) -> (Array2<BitValue>, usize) {
+    let middle = A_MAX as f64 / 2.0;
+    let scale = |n: i64| (n as f64 * contrast).round() as i64;
+    let mut saturated = 0;
+    for (_r, _c, block) in codewords.iter_row_major_mut() {
+        let a = ((block.a as f64 - middle) * contrast + middle).round() as i64;
+        block.a = saturate(a, 0, A_MAX, &mut saturated) as u64;
+        block.b = saturate(scale(block.b), -BCD_MAX, BCD_MAX, &mut saturated);
+        block.c = saturate(scale(block.c), -BCD_MAX, BCD_MAX, &mut saturated);
+        block.d = saturate(scale(block.d), -BCD_MAX, BCD_MAX, &mut saturated);
+    }
+    (codewords, saturated)
+}
+
+/// Converts the image to grayscale without decoding it by setting the chroma indices of
+/// every block to zero chroma. The chroma table has no exact zero, only the entries
+/// either side of it, so neighbouring blocks alternate between the two in a checkerboard
+/// and the chroma of the image averages out to zero instead of tinting it.
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+pub fn desaturate(mut codewords: Array2<BitValue>) -> Array2<BitValue> {
+    let gray = [index_of_chroma(-GRAY_CHROMA), index_of_chroma(GRAY_CHROMA)];
+    for (row, col, block) in codewords.iter_row_major_mut() {
+        block.index_pb = gray[(row + col) % 2];
+        block.index_pr = gray[(row + col) % 2];
+    }
+    codewords
+}
+
+/// Changes the saturation of the image without decoding it by scaling the chroma of every
+/// block and looking the result back up in the chroma table. A `saturation` of 0.0 is
+/// handed to `desaturate`, since every chroma would otherwise land on the same entry just
+/// below zero. Returns the adjusted blocks along with the number of chroma values that
+/// saturated at the ends of the table.
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+/// * `saturation`: the factor to scale the chroma by, where 1.0 leaves the image unchanged
+///
+/// # Panics
+///
+/// Panics if `saturation` is negative
+pub fn adjust_saturation(mut codewords: Array2<BitValue>, saturation: f64) -> (Array2<BitValue>, usize) {
+    assert!(saturation >= 0.0, "saturation must not be negative");
+    if saturation == 0.0 {
+        return (desaturate(codewords), 0);
+    }
+    let mut saturated = 0;
+    let mut scale = |index: usize| {
+        let chroma = chroma_of_index(index) * saturation as f32;
+        if chroma.abs() > CHROMA_MAX {
+            saturated += 1;
+        }
+        // the table lookup only finds the nearest entry for chroma within the table, so
+        // anything past either end is clamped to that end first
+        index_of_chroma(chroma.clamp(-CHROMA_MAX, CHROMA_MAX))
+    };
+    for (_r, _c, block) in codewords.iter_row_major_mut() {
+        block.index_pb = scale(block.index_pb);
+        block.index_pr = scale(block.index_pr);
+    }
+    (codewords, saturated)
+}
@@ -1,5 +1,5 @@
 use crate::pixel_converter::ComponentVideo;
-use array2::Array2;
+use array2::{Array2, EdgePolicy};
 
 /// A `Block` stores data taken by a 2x2 matrix of component video pixels. It has
 /// a 4 element array that holds the different `f64` luminance values of the pixels,
@@ -40,14 +40,10 @@ fn deconstruct_block(block: Block) -> [ComponentVideo; 4] {
 /// * `image`: an array2 that is holding component video pixels.
 pub fn component_video_to_blocks(image: Array2<ComponentVideo>) -> Array2<Block> {
     let mut block_vec = vec![];
-    for i in 0..image.height()/2 {
-        for j in 0..image.width()/2 {
-            // indexing to get the pixels from 2x2 blocks
-            let arr = [image.get(2*i, 2*j).unwrap().clone(), image.get(2*i, 2*j+1).unwrap().clone(),
-            image.get(2*i+1, 2*j).unwrap().clone(), image.get(2*i+1, 2*j+1).unwrap().clone()];
-            // pushes to vector in row-major order
-            block_vec.push(create_block(arr));
-        }
+    for (_r, _c, tile) in image.blocks(2, 2, EdgePolicy::Skip) {
+        let arr = [tile[(0, 0)].clone(), tile[(0, 1)].clone(), tile[(1, 0)].clone(), tile[(1, 1)].clone()];
+        // pushes to vector in row-major order
+        block_vec.push(create_block(arr));
     }
     Array2::from_row_major(block_vec, image.width()/2, image.height()/2)
 }
@@ -60,14 +56,11 @@ pub fn component_video_to_blocks(image: Array2<ComponentVideo>) -> Array2<Block>
 pub fn blocks_to_component_video(blocks: Array2<Block>) -> Array2<ComponentVideo> {
     let mut array2_vid = Array2::from_blank_state(
         ComponentVideo {y: 0.0, pb: 0.0, pr: 0.0}, blocks.width()*2, blocks.height()*2);
-    for i in 0..blocks.height() {
-        for j in 0..blocks.width() {
-            let arr = deconstruct_block(blocks.get(i,j).unwrap().clone());
-            // indexing correctly back into the ComponentVideo array2
-            array2_vid.insert(2*i, 2*j, arr[0].clone());
-            array2_vid.insert(2*i, 2*j+1, arr[1].clone());
-            array2_vid.insert(2*i+1, 2*j, arr[2].clone());
-            array2_vid.insert(2*i+1, 2*j+1, arr[3].clone());
+    // the 2x2 tiles of the pixels are in the same row-major order as the blocks
+    for ((_r, _c, mut tile), (_, _, block)) in array2_vid.blocks_mut(2, 2, EdgePolicy::Skip).zip(blocks.iter_row_major()) {
+        let arr = deconstruct_block(block.clone());
+        for (r, c, pixel) in tile.iter_row_major_mut() {
+            *pixel = arr[r * 2 + c].clone();
         }
     }
     array2_vid
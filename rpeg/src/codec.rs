@@ -1,74 +1,380 @@
 use csc411_image::{RgbImage, Read, Write, Rgb};
 use array2::Array2;
+use crate::adjust::*;
 use crate::pixel_converter::*;
 use crate::block::*;
 use crate::dct::*;
+use crate::deblock::*;
 use crate::quantization::*;
 use crate::codeword::*;
+use crate::mosaic::*;
+use crate::motion::*;
+use crate::progressive::*;
+use crate::transform::*;
 
 /// Compresses the image and outputs the compressed image to stdout
 /// 
 /// # Arguments
 /// 
 /// * `filename`: An optional file path, if `None` read from standard in
-pub fn compress(filename: Option<&str>) {
-    let image = RgbImage::read(filename).unwrap();
-    let image_as_array2 = trim_image(&image);
-    // call upon compression functions
-    // function operations in order: convert to CV, create 2x2 blocks, convert to cosine space, 
-    // quantization, create codewords and output them
-    output_all_codewords(
-        convert_to_bits(
-            to_cosine_space(
-                component_video_to_blocks(
-                    rgb_image_to_component_video(image_as_array2, image.denominator)
-                )
-            )
-        )
-    );
+/// * `params`: options for how the image is quantized
+pub fn compress(filename: Option<&str>, params: QuantizationParams) {
+    output_all_codewords(image_to_codewords(&RgbImage::read(filename).unwrap(), params));
+}
+
+/// Compresses the image and outputs it to stdout in the progressive layout, where the
+/// average luminance and chroma of every block come before any of the detail coefficients
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+/// * `params`: options for how the image is quantized
+pub fn compress_progressive(filename: Option<&str>, params: QuantizationParams) {
+    output_progressive(image_to_codewords(&RgbImage::read(filename).unwrap(), params));
 }
+
 /// Decompresses the image and outputs the decompressed image to stdout
 /// 
 /// # Arguments
 /// 
 /// * `filename`: An optional file path, if `None` read from standard in
-pub fn decompress(filename: Option<&str>) {
-    // call upon decompression functions:
-    // function operations in order: Read in 32-bit codewords and convert back to quantized values,
-    // convert to cosine space, back to pixel space, unpack the 2x2 blocks, 
-    // back to rgb pixels with denominator 255
-    let image_as_array2 = component_video_image_to_rgb(
-        blocks_to_component_video(
-            to_pixels(
-                convert_to_cs(
-                    read_all_codewords(filename)
-                )
+/// * `deblocking`: whether to hide the 2x2 block structure with the deblocking filter
+pub fn decompress(filename: Option<&str>, deblocking: bool) {
+    output_image(codewords_to_rgb(read_all_codewords(filename), deblocking));
+}
+
+/// Decompresses an image stored in the progressive layout and outputs it to stdout. If the
+/// file has been cut off after the first pass, the blocks without detail coefficients are
+/// decompressed as flat blocks.
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+/// * `deblocking`: whether to hide the 2x2 block structure with the deblocking filter
+pub fn decompress_progressive(filename: Option<&str>, deblocking: bool) {
+    output_image(codewords_to_rgb(read_progressive(filename), deblocking));
+}
+
+/// Decompresses only the `width` x `height` window of the image whose top left
+/// pixel is at (`x`, `y`), and outputs that window to stdout. Only the codewords
+/// of the 2x2 blocks covering the window are read from the file, along with one
+/// more block on every side when deblocking, since the filter reads that far.
+/// The window is then the same as that window of the whole decoded image.
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+/// * `x`: the column of the top left pixel of the window
+/// * `y`: the row of the top left pixel of the window
+/// * `width`: the width of the window in pixels
+/// * `height`: the height of the window in pixels
+/// * `deblocking`: whether to hide the 2x2 block structure with the deblocking filter
+pub fn decode_region(filename: Option<&str>, x: usize, y: usize, width: usize, height: usize, deblocking: bool) {
+    assert!(width > 0 && height > 0, "region must not be empty");
+    // the window is widened to the 2x2 blocks that cover it
+    let block_col = x/2;
+    let block_row = y/2;
+    let blocks_wide = (x + width).div_ceil(2) - block_col;
+    let blocks_high = (y + height).div_ceil(2) - block_row;
+    let margin = if deblocking { 1 } else { 0 };
+    let (codewords, top, left) = read_region_codewords(filename, block_row, block_col, blocks_high, blocks_wide, margin);
+    let covering = codewords_to_rgb(codewords, deblocking);
+    // then cut back down to the exact window that was asked for
+    output_image(covering.view(top*2 + y%2, left*2 + x%2, height, width).to_array2());
+}
+
+/// Decompresses the image at half resolution and outputs it to stdout. Each pixel of
+/// the thumbnail is the average luminance and chroma of one 2x2 block, so the detail
+/// coefficients are never transformed back to pixel space.
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+pub fn decode_thumbnail(filename: Option<&str>) {
+    output_image(component_video_image_to_rgb(convert_to_averages(read_all_codewords(filename)), 255));
+}
+
+/// Rotates, flips, and transposes a compressed image without decoding it, and outputs
+/// the new compressed image to stdout. The operations are applied in that order.
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+/// * `rotation`: an optional clockwise rotation
+/// * `mirror`: an optional flip
+/// * `swap_axes`: whether to transpose the image
+pub fn transform(filename: Option<&str>, rotation: Option<Rotation>, mirror: Option<Flip>, swap_axes: bool) {
+    let mut codewords = read_all_codewords(filename);
+    if let Some(rotation) = rotation {
+        codewords = rotate(codewords, rotation);
+    }
+    if let Some(mirror) = mirror {
+        codewords = flip(codewords, mirror);
+    }
+    if swap_axes {
+        codewords = transpose(codewords);
+    }
+    output_all_codewords(codewords);
+}
+
+/// Crops a compressed image without decoding it, and outputs the new compressed image
+/// to stdout. The crop must be aligned to the 2x2 blocks, so every argument must be even.
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+/// * `x`: the column of the top left pixel of the crop
+/// * `y`: the row of the top left pixel of the crop
+/// * `width`: the width of the crop in pixels
+/// * `height`: the height of the crop in pixels
+pub fn crop_image(filename: Option<&str>, x: usize, y: usize, width: usize, height: usize) {
+    assert!([x, y, width, height].iter().all(|n| n.is_multiple_of(2)), "crop must be aligned to the 2x2 blocks");
+    output_all_codewords(crop(read_all_codewords(filename), y/2, x/2, height/2, width/2));
+}
+
+/// Halves the width and height of a compressed image without decoding it to pixels, and
+/// outputs the new compressed image to stdout
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+pub fn downscale_image(filename: Option<&str>) {
+    output_all_codewords(downscale(read_all_codewords(filename)));
+}
+
+/// Changes the brightness, contrast, and saturation of a compressed image without decoding
+/// it, and outputs the new compressed image to stdout. The number of coefficients that
+/// saturated is reported on stderr.
+/// 
+/// # Arguments
+/// 
+/// * `filename`: An optional file path, if `None` read from standard in
+/// * `brightness`: the amount of luminance to add, where 1.0 is the full range
+/// * `contrast`: the factor to scale the contrast by, where 1.0 leaves the image unchanged
+/// * `saturation`: the factor to scale the chroma by, where 0.0 makes the image grayscale
+pub fn adjust(filename: Option<&str>, brightness: f64, contrast: f64, saturation: f64) {
+    let (codewords, contrast_saturated) = adjust_contrast(read_all_codewords(filename), contrast);
+    let (codewords, brightness_saturated) = adjust_brightness(codewords, brightness);
+    let (codewords, chroma_saturated) = adjust_saturation(codewords, saturation);
+    eprintln!("{} coefficients saturated", contrast_saturated + brightness_saturated + chroma_saturated);
+    output_all_codewords(codewords);
+}
+
+/// Places several compressed images into one grid without decoding them, and outputs
+/// the mosaic as a single compressed image to stdout
+/// 
+/// # Arguments
+/// 
+/// * `filenames`: file paths of the compressed images, in row-major order of the grid
+/// * `columns`: the number of images placed side by side in each row of the grid
+/// * `fill`: the colour, with denominator 255, of any space not covered by an image
+pub fn tile_images(filenames: &[&str], columns: usize, fill: Rgb) {
+    let images = filenames.iter().map(|filename| read_all_codewords(Some(filename))).collect();
+    output_all_codewords(tile(images, columns, &solid_codeword(fill, 255)));
+}
+
+/// Adds a compressed image to the end of a motion rpeg container as a new frame, storing
+/// only the blocks that changed since the previous frame when it is not a keyframe
+/// 
+/// # Arguments
+/// 
+/// * `container`: the file path of the container, which is created if it does not exist
+/// * `filename`: An optional file path of the frame, if `None` read from standard in
+/// * `timestamp`: the time the frame was captured
+/// * `keyframe_interval`: the most frames from one keyframe to the next, only used when
+///   the container is created
+pub fn append_to_sequence(container: &str, filename: Option<&str>, timestamp: u64, keyframe_interval: usize) {
+    append_frame(container, Frame {timestamp, codewords: read_all_codewords(filename)}, keyframe_interval);
+}
+
+/// Outputs the frame index table of a motion rpeg container to stdout, one frame per line
+/// marked `I` for keyframes and `P` for delta frames
+/// 
+/// # Arguments
+/// 
+/// * `container`: the file path of the container
+pub fn list_sequence(container: &str) {
+    for (index, entry) in list_frames(container).iter().enumerate() {
+        let kind = if entry.kind == FrameKind::Delta { "P" } else { "I" };
+        println!("{} {} {}x{} {}", index, entry.timestamp, entry.width, entry.height, kind);
+    }
+}
+
+/// Outputs a single frame of a motion rpeg container to stdout as a compressed image
+/// 
+/// # Arguments
+/// 
+/// * `container`: the file path of the container
+/// * `index`: the position of the frame in the sequence, starting at 0
+pub fn extract_from_sequence(container: &str, index: usize) {
+    output_all_codewords(read_frame(container, index).codewords);
+}
+
+// Compression functions in order: convert to CV, create 2x2 blocks, convert to cosine space, 
+// quantization
+fn image_to_codewords(image: &RgbImage, params: QuantizationParams) -> Array2<BitValue> {
+    let image_as_array2 = trim_image(image);
+    convert_to_bits_with(
+        to_cosine_space(
+            component_video_to_blocks(
+                rgb_image_to_component_video(image_as_array2, image.denominator)
             )
-        ), 
-    255);
+        ),
+        params
+    )
+}
+
+// Decompression functions in order: convert the 32-bit codewords back to quantized values,
+// convert to cosine space, back to pixel space, unpack the 2x2 blocks (smoothing across
+// them when deblocking), back to rgb pixels with denominator 255
+fn codewords_to_rgb(codewords: Array2<BitValue>, deblocking: bool) -> Array2<Rgb> {
+    let blocks = to_pixels(convert_to_cs(codewords));
+    component_video_image_to_rgb(
+        if deblocking { deblock(blocks) } else { blocks_to_component_video(blocks) },
+    255)
+}
+
+// Outputs an Array2 of Rgb pixels with denominator 255 to stdout
+fn output_image(image_as_array2: Array2<Rgb>) {
     let image = RgbImage{
-        pixels: image_as_array2
-        .iter_row_major()
-        .map(|data| data.2.clone())
-        .collect(),
         width: image_as_array2.width() as u32,
         height: image_as_array2.height() as u32,
+        pixels: image_as_array2.into_vec(),
         denominator: 255
     };
     image.write(None).unwrap();
-
 }
 
 // Trims the image to make sure that the height and width are even, and then stores it in an Array2
 fn trim_image(image: &RgbImage) -> Array2<Rgb> {
     let mut new_width = image.width;
     let mut new_height = image.height;
-    if image.width % 2 != 0 {
+    if !image.width.is_multiple_of(2) {
         new_width = image.width-1;
     }
-    if image.height % 2 != 0 {
+    if !image.height.is_multiple_of(2) {
         new_height = image.height-1;
     }
 
-    Array2::from_row_major(image.pixels.clone(), new_width as usize, new_height as usize)
+    // the pixels are stored in rows of the original width, so the dropped column is cut
+    // from the end of every row
+    Array2::from_row_major_truncating(image.pixels.clone(), image.width as usize, new_width as usize, new_height as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::ssim;
+
+    // A textured image with colour that changes in every direction, so no two blocks match
+    fn test_image(width: u32, height: u32) -> RgbImage {
+        let pixels = (0..width * height).map(|index| {
+            let (row, col) = ((index / width) as u16, (index % width) as u16);
+            Rgb {red: (col * 37 + row * 11) % 256, green: (row * 23 + col * col) % 256, blue: (col * row * 7 + 40) % 256}
+        }).collect();
+        RgbImage {pixels, width, height, denominator: 255}
+    }
+
+    fn channels(image: &Array2<Rgb>) -> Vec<(u16, u16, u16)> {
+        image.iter_row_major().map(|(_r, _c, pixel)| (pixel.red, pixel.green, pixel.blue)).collect()
+    }
+
+    #[test]
+    fn perceptual_quantization_keeps_more_structure_than_rounding() {
+        // a smooth ramp, where every luminance error shows, and a busy texture that masks them
+        let ramp = RgbImage {
+            pixels: (0..32 * 24).map(|index| {
+                let (row, col) = ((index / 32) as u16, (index % 32) as u16);
+                Rgb {red: 40 + col * 5, green: 30 + row * 7, blue: 100 + (col + row) * 2}
+            }).collect(),
+            width: 32, height: 24, denominator: 255,
+        };
+        for image in [ramp, test_image(32, 24)] {
+            let original = trim_image(&image);
+            let decode = |params| codewords_to_rgb(image_to_codewords(&image, params), false);
+            let rounded = decode(QuantizationParams::default());
+            let perceptual = decode(QuantizationParams {perceptual: true, ..Default::default()});
+            assert!(ssim(&original, 255, &perceptual, 255) > ssim(&original, 255, &rounded, 255));
+        }
+    }
+
+    #[test]
+    fn desaturated_image_has_no_colour_cast() {
+        let codewords = image_to_codewords(&test_image(12, 10), QuantizationParams::default());
+        let (desaturated, saturated) = adjust_saturation(codewords, 0.0);
+        assert_eq!(saturated, 0);
+        for deblocking in [false, true] {
+            let pixels = channels(&codewords_to_rgb(desaturated.clone(), deblocking));
+            let mean = |channel: fn(&(u16, u16, u16)) -> u16| {
+                pixels.iter().map(|pixel| channel(pixel) as f64).sum::<f64>() / pixels.len() as f64
+            };
+            let (red, green, blue) = (mean(|pixel| pixel.0), mean(|pixel| pixel.1), mean(|pixel| pixel.2));
+            assert!((red - green).abs() < 0.5 && (blue - green).abs() < 0.5, "cast of {} {} {}", red, green, blue);
+        }
+    }
+
+    #[test]
+    fn decoded_crop_equals_crop_of_decoded_original() {
+        let codewords = image_to_codewords(&test_image(12, 10), QuantizationParams::default());
+        let decoded = codewords_to_rgb(codewords.clone(), false);
+        let cropped = codewords_to_rgb(crop(codewords, 1, 2, 3, 2), false);
+        assert_eq!(channels(&cropped), channels(&decoded.view(2, 4, 6, 4).to_array2()));
+    }
+
+    #[test]
+    fn decoded_transform_equals_transform_of_decoded_original() {
+        let codewords = image_to_codewords(&test_image(12, 10), QuantizationParams::default());
+        let decoded = codewords_to_rgb(codewords.clone(), false);
+        let (width, height) = (decoded.width(), decoded.height());
+        // maps the (row, column) of a pixel of the output to the pixel it comes from
+        type Source = Box<dyn Fn(usize, usize) -> (usize, usize)>;
+        let cases: [(Array2<BitValue>, usize, usize, Source); 6] = [
+            (rotate(codewords.clone(), Rotation::Rotate90), height, width, Box::new(move |r, c| (height - 1 - c, r))),
+            (rotate(codewords.clone(), Rotation::Rotate180), width, height, Box::new(move |r, c| (height - 1 - r, width - 1 - c))),
+            (rotate(codewords.clone(), Rotation::Rotate270), height, width, Box::new(move |r, c| (c, width - 1 - r))),
+            (flip(codewords.clone(), Flip::Horizontal), width, height, Box::new(move |r, c| (r, width - 1 - c))),
+            (flip(codewords.clone(), Flip::Vertical), width, height, Box::new(move |r, c| (height - 1 - r, c))),
+            (transpose(codewords), height, width, Box::new(move |r, c| (c, r))),
+        ];
+        for (transformed, expected_width, expected_height, source) in cases {
+            let expected = Array2::from_fn(expected_width, expected_height, |r, c| decoded[source(r, c)].clone());
+            let transformed = codewords_to_rgb(transformed, false);
+            assert_eq!((transformed.width(), transformed.height()), (expected_width, expected_height));
+            assert_eq!(channels(&transformed), channels(&expected));
+        }
+    }
+
+    #[test]
+    fn progressive_file_decodes_the_same_as_the_plain_one() {
+        let codewords = image_to_codewords(&test_image(12, 10), QuantizationParams::default());
+        let plain = unpack_codewords(&pack_codewords(&codewords), codewords.width(), codewords.height());
+        let mut file = vec![];
+        write_progressive(&codewords, &mut file);
+        let progressive = parse_progressive(&file[..]);
+        for deblocking in [false, true] {
+            assert_eq!(channels(&codewords_to_rgb(progressive.clone(), deblocking)),
+                channels(&codewords_to_rgb(plain.clone(), deblocking)));
+        }
+    }
+
+    #[test]
+    fn progressive_file_cut_after_the_first_pass_has_flat_blocks() {
+        let codewords = image_to_codewords(&test_image(12, 10), QuantizationParams::default());
+        let mut file = vec![];
+        write_progressive(&codewords, &mut file);
+        // the header, then 17 bits of `a` and the chroma indices for each of the 30 blocks
+        let first_pass = "Progressive image format 2\n12 10\n".len() + (30 * 17_usize).div_ceil(8);
+        // two more bytes hold the detail of the first block, but not all of the second
+        for (length, detailed) in [(first_pass, 0), (first_pass + 2, 1)] {
+            let preview = parse_progressive(&file[..length]);
+            for ((_, _, read), (_, _, original)) in preview.iter_row_major().zip(codewords.iter_row_major()) {
+                assert_eq!((read.a, read.index_pb, read.index_pr), (original.a, original.index_pb, original.index_pr));
+            }
+            let details: Vec<(i64, i64, i64)> = preview.iter_row_major().map(|(_, _, read)| (read.b, read.c, read.d)).collect();
+            let expected: Vec<(i64, i64, i64)> = codewords.iter_row_major().enumerate()
+                .map(|(index, (_, _, block))| if index < detailed { (block.b, block.c, block.d) } else { (0, 0, 0) })
+                .collect();
+            assert_eq!(details, expected);
+        }
+    }
 }
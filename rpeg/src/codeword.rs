@@ -2,6 +2,8 @@ use bitpack::bitpack::{newu, news, gets, getu};
 use array2::Array2;
 use crate::quantization::*;
 use csc411_rpegio::*;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 
 // Prints the codewords utilizing rpegio
 fn print_codewords(bytes: Vec<[u8; 4]>, width: usize, height: usize) {
@@ -48,11 +50,7 @@ fn bytes_to_codeword(bytes: [u8; 4]) -> u32 {
 /// 
 /// * `quantized_block`: an array2 of 2x2 blocks of quantized values that make up the 32 bit codeword
 pub fn output_all_codewords(quantized_block: Array2<BitValue>) {
-    let mut byte_vec = vec![];
-    for (_r, _c, block) in quantized_block.iter_row_major() {
-        byte_vec.push(codeword_to_bytes(create_codeword(block).try_into().unwrap()));
-    }
-    print_codewords(byte_vec, quantized_block.width()*2, quantized_block.height()*2);
+    print_codewords(pack_codewords(&quantized_block), quantized_block.width()*2, quantized_block.height()*2);
 }
 
 /// Reads the codewords from standard in or a file, 
@@ -63,11 +61,115 @@ pub fn output_all_codewords(quantized_block: Array2<BitValue>) {
 /// * `file_path`: An optional file path, if `None` read from standard in
 pub fn read_all_codewords(file_path: Option<&str>) -> Array2<BitValue> {
     let input_data = read_in_rpeg_data(file_path).unwrap();
+    unpack_codewords(&input_data.0, (input_data.1/2) as usize, (input_data.2/2) as usize)
+}
+
+/// Constructs the codewords of the quantized 2x2 blocks as bytes, in row-major order
+/// 
+/// # Arguments
+/// 
+/// * `quantized_block`: an array2 of 2x2 blocks of quantized values that make up the 32 bit codeword
+pub fn pack_codewords(quantized_block: &Array2<BitValue>) -> Vec<[u8; 4]> {
+    let mut byte_vec = vec![];
+    for (_r, _c, block) in quantized_block.iter_row_major() {
+        byte_vec.push(codeword_to_bytes(create_codeword(block)));
+    }
+    byte_vec
+}
+
+/// Converts codewords stored as bytes in row-major order back to 2x2 blocks of quantized values
+/// 
+/// # Arguments
+/// 
+/// * `bytes`: the codewords, 4 bytes per block
+/// * `width`: the number of block columns
+/// * `height`: the number of block rows
+pub fn unpack_codewords(bytes: &[[u8; 4]], width: usize, height: usize) -> Array2<BitValue> {
     let mut quantized_values = vec![];
-    for row in 0..input_data.2/2 {
-        for col in 0..input_data.1/2 {
-            quantized_values.push(deconstruct_codeword(bytes_to_codeword(input_data.0[(row * input_data.1/2 + col) as usize])));
+    for row in 0..height {
+        for col in 0..width {
+            quantized_values.push(deconstruct_codeword(bytes_to_codeword(bytes[row * width + col])));
         }
     }
-    Array2::from_row_major(quantized_values, (input_data.1/2) as usize, (input_data.2/2) as usize)
-}
\ No newline at end of file
+    Array2::from_row_major(quantized_values, width, height)
+}
+
+/// Reads only the codewords of a `width` x `height` rectangle of 2x2 blocks whose top left
+/// block is at (`row`, `col`), widened by up to `margin` blocks on every side that lies
+/// inside the image, and converts them back to 2x2 blocks of quantized values. Returns the
+/// blocks along with how many block rows and block columns the margin added above and to
+/// the left of the rectangle. When reading from a file, each row is read by seeking
+/// directly to its codewords, since every block is stored as 4 bytes in row-major order
+/// after the header.
+/// 
+/// # Arguments
+/// 
+/// * `file_path`: An optional file path, if `None` read from standard in
+/// * `row`: the block row of the top left block of the rectangle
+/// * `col`: the block column of the top left block of the rectangle
+/// * `height`: the number of block rows in the rectangle
+/// * `width`: the number of block columns in the rectangle
+/// * `margin`: the number of extra blocks to read around the rectangle where the image allows
+pub fn read_region_codewords(file_path: Option<&str>, row: usize, col: usize, height: usize, width: usize, margin: usize) -> (Array2<BitValue>, usize, usize) {
+    let mut quantized_values = vec![];
+    let (first_row, first_col, rows, cols) = match file_path {
+        Some(path) => {
+            let mut reader = BufReader::new(File::open(path).unwrap());
+            let (image_width, image_height, header_len) = read_rpeg_header(&mut reader);
+            let widened = widen_region(image_width, image_height, row, col, height, width, margin);
+            let (first_row, first_col, rows, cols) = widened;
+            let mut bytes = vec![0_u8; cols*4];
+            for block_row in first_row..first_row+rows {
+                let offset = header_len + ((block_row * image_width/2 + first_col) * 4) as u64;
+                reader.seek(SeekFrom::Start(offset)).unwrap();
+                reader.read_exact(&mut bytes).unwrap();
+                for codeword in bytes.chunks_exact(4) {
+                    quantized_values.push(deconstruct_codeword(bytes_to_codeword(codeword.try_into().unwrap())));
+                }
+            }
+            widened
+        }
+        None => {
+            // standard in cannot seek, so the whole image has to be read
+            let input_data = read_in_rpeg_data(None).unwrap();
+            let image_width = input_data.1 as usize;
+            let widened = widen_region(image_width, input_data.2 as usize, row, col, height, width, margin);
+            let (first_row, first_col, rows, cols) = widened;
+            for block_row in first_row..first_row+rows {
+                for block_col in first_col..first_col+cols {
+                    quantized_values.push(deconstruct_codeword(bytes_to_codeword(input_data.0[block_row * image_width/2 + block_col])));
+                }
+            }
+            widened
+        }
+    };
+    (Array2::from_row_major(quantized_values, cols, rows), row - first_row, col - first_col)
+}
+
+// Reads the rpeg header, returning the image width, height, and the length of the header in bytes
+fn read_rpeg_header(reader: &mut impl BufRead) -> (usize, usize, u64) {
+    let mut format_line = String::new();
+    let mut size_line = String::new();
+    let header_len = reader.read_line(&mut format_line).unwrap() + reader.read_line(&mut size_line).unwrap();
+    assert!(format_line.trim_end() == "Compressed image format 2", "not an rpeg file");
+    let size: Vec<usize> = size_line.split_whitespace().map(|num| num.parse().unwrap()).collect();
+    assert!(size.len() == 2, "badly formatted rpeg header");
+    (size[0], size[1], header_len as u64)
+}
+
+// Panics if the rectangle of blocks does not lie within an image of the given size in pixels
+fn check_region(image_width: usize, image_height: usize, row: usize, col: usize, height: usize, width: usize) {
+    assert!((row + height) * 2 <= image_height && (col + width) * 2 <= image_width,
+        "region lies outside the {}x{} image", image_width, image_height);
+}
+
+// Checks the block rectangle fits inside a width x height pixel image, and returns it widened
+// by up to `margin` blocks on every side, stopping at the edges of the image, as
+// (row, col, height, width)
+fn widen_region(image_width: usize, image_height: usize, row: usize, col: usize, height: usize, width: usize, margin: usize) -> (usize, usize, usize, usize) {
+    check_region(image_width, image_height, row, col, height, width);
+    let (top, left) = (row.min(margin), col.min(margin));
+    let bottom = margin.min(image_height/2 - row - height);
+    let right = margin.min(image_width/2 - col - width);
+    (row - top, col - left, top + height + bottom, left + width + right)
+}
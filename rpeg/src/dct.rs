@@ -56,8 +56,12 @@ pub fn to_cosine_space(mut vid_blocks: Array2<Block>) -> Array2<Block> {
 pub fn to_pixels(mut cs_blocks: Array2<Block>) -> Array2<Block> {
     for (_r, _c, cs_2x2) in cs_blocks.iter_row_major_mut() {
         // transforming to pixels
-        cs_2x2.luminance = [calculate_y1(cs_2x2.luminance), calculate_y2(cs_2x2.luminance),
-        calculate_y3(cs_2x2.luminance), calculate_y4(cs_2x2.luminance)];
+        cs_2x2.luminance = cosine_to_pixels(cs_2x2.luminance);
     }
     cs_blocks
+}
+
+// Transforms the a, b, c, d values of a single block back to its four y values
+pub(crate) fn cosine_to_pixels(luminance: [f64; 4]) -> [f64; 4] {
+    [calculate_y1(luminance), calculate_y2(luminance), calculate_y3(luminance), calculate_y4(luminance)]
 }
\ No newline at end of file
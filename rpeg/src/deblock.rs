@@ -0,0 +1,82 @@
+use crate::block::Block;
+use crate::pixel_converter::ComponentVideo;
+use array2::{Array2, EdgeMode};
+
+// The largest luminance step between pixels or blocks that is smoothed. Larger steps are
+// taken to be real edges in the image and are left alone.
+const EDGE_THRESHOLD: f32 = 0.08;
+
+// Returns the row or column of the 3x3 neighbourhood of a pixel's block that holds the
+// block on the far side of pixel `index` from the centre of its own block
+fn neighbour_block(index: usize) -> usize {
+    if index.is_multiple_of(2) { 0 } else { 2 }
+}
+
+// Returns the average luminance of a block in pixel space
+fn average_luminance(block: &Block) -> f32 {
+    (block.luminance.iter().sum::<f64>() / 4.0) as f32
+}
+
+// Interpolates the average chroma of the blocks bilinearly between block centres. Every
+// pixel sits a quarter of a block from its own block's centre, so it takes 3/4 of its own
+// block and 1/4 of the neighbouring block in each direction. A neighbour across a real
+// edge in the image contributes the pixel's own block's chroma instead, so colours do not
+// bleed across the edge. The edge blocks are repeated past the sides of the image.
+fn upsample_chroma(blocks: &Array2<Block>, row: usize, col: usize) -> (f32, f32) {
+    let neighbours = blocks.neighbourhood(row / 2, col / 2, 3, 3, &EdgeMode::Replicate);
+    let own = &neighbours[(1, 1)];
+    let rows = [1, neighbour_block(row)];
+    let cols = [1, neighbour_block(col)];
+    let weights = [0.75, 0.25];
+    let (mut pb, mut pr) = (0.0, 0.0);
+    for (block_row, row_weight) in rows.iter().zip(weights) {
+        for (block_col, col_weight) in cols.iter().zip(weights) {
+            let mut block = &neighbours[(*block_row, *block_col)];
+            if (average_luminance(block) - average_luminance(own)).abs() >= EDGE_THRESHOLD {
+                block = own;
+            }
+            pb += row_weight * col_weight * block.pb_avg;
+            pr += row_weight * col_weight * block.pr_avg;
+        }
+    }
+    (pb, pr)
+}
+
+// Pulls the two pixels either side of a block boundary a quarter of the way towards each
+// other, unless the step between them is large enough to be a real edge
+fn smooth_boundary(image: &mut Array2<ComponentVideo>, before: (usize, usize), after: (usize, usize)) {
+    let step = image[after].y - image[before].y;
+    if step.abs() < EDGE_THRESHOLD {
+        image[before].y += step / 4.0;
+        image[after].y -= step / 4.0;
+    }
+}
+
+/// Creates an Array2 of component video pixels from all of the blocks like
+/// `blocks_to_component_video`, but hides the block structure: chroma is interpolated
+/// bilinearly between block centres instead of repeated across each block, and small
+/// luminance steps across block boundaries are smoothed.
+///
+/// # Arguments
+///
+/// * `blocks`: an array2 that holds all 2x2 blocks in pixel space
+pub fn deblock(blocks: Array2<Block>) -> Array2<ComponentVideo> {
+    let (width, height) = (blocks.width()*2, blocks.height()*2);
+    let mut image = Array2::from_fn(width, height, |row, col| {
+        let block = &blocks[(row / 2, col / 2)];
+        let (pb, pr) = upsample_chroma(&blocks, row, col);
+        ComponentVideo {y: block.luminance[(row % 2) * 2 + col % 2] as f32, pb, pr}
+    });
+    // vertical boundaries between block columns, then horizontal boundaries between block rows
+    for row in 0..height {
+        for col in (2..width).step_by(2) {
+            smooth_boundary(&mut image, (row, col - 1), (row, col));
+        }
+    }
+    for row in (2..height).step_by(2) {
+        for col in 0..width {
+            smooth_boundary(&mut image, (row - 1, col), (row, col));
+        }
+    }
+    image
+}
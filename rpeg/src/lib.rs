@@ -1,6 +1,13 @@
+pub mod adjust;
 pub mod block;
 pub mod codec;
 pub mod dct;
+pub mod deblock;
+pub mod metrics;
+pub mod mosaic;
+pub mod motion;
 pub mod pixel_converter;
+pub mod progressive;
 pub mod quantization;
-pub mod codeword;
\ No newline at end of file
+pub mod codeword;
+pub mod transform;
\ No newline at end of file
@@ -1,15 +1,135 @@
-use rpeg::codec::{compress, decompress};
+use rpeg::codec::*;
+use rpeg::quantization::QuantizationParams;
+use rpeg::transform::{Flip, Rotation};
+use csc411_image::Rgb;
+use std::collections::HashMap;
 use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "Usage: rpeg -d [--deblock] [--region x,y,width,height | --thumbnail | --progressive] [filename]
+rpeg -c [--rdo | --perceptual] [--progressive] [filename]
+rpeg transform [--rotate 90|180|270] [--flip h|v] [--transpose] [filename]
+rpeg crop --region x,y,width,height [filename]
+rpeg downscale [filename]
+rpeg adjust [--brightness amount] [--contrast factor] [--saturation factor | --desaturate] [filename]
+rpeg tile [--columns n] [--fill red,green,blue] filename...
+rpeg motion append [--timestamp ms] [--keyframe-interval n] container [filename]
+rpeg motion list container
+rpeg motion extract --frame n container";
+
+// Options that are given without a value
+const FLAGS: [&str; 7] = ["--rdo", "--perceptual", "--deblock", "--thumbnail", "--progressive", "--transpose", "--desaturate"];
+
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let argnum = args.len();
-    assert!(argnum == 2 || argnum == 3);
-    let filename = args.iter().nth(2).unwrap();
+    assert!(args.len() >= 2, "{}", USAGE);
+    let (options, files) = parse_args(&args[2..]);
+    let filename = files.first().copied();
+    let params = QuantizationParams {
+        rate_distortion: options.contains_key("--rdo"),
+        perceptual: options.contains_key("--perceptual"),
+    };
+    let deblocking = options.contains_key("--deblock");
     match args[1].as_str() {
-        "-c" => compress(Some(filename)),
-        "-d" => decompress(Some(filename)),
+        "-c" if options.contains_key("--progressive") => compress_progressive(filename, params),
+        "-c" => compress(filename, params),
+        "-d" => match options.get("--region") {
+            Some(region) => {
+                let region = parse_region(region);
+                decode_region(filename, region[0], region[1], region[2], region[3], deblocking)
+            }
+            None if options.contains_key("--thumbnail") => decode_thumbnail(filename),
+            None if options.contains_key("--progressive") => decompress_progressive(filename, deblocking),
+            None => decompress(filename, deblocking),
+        },
+        "transform" => {
+            let rotation = options.get("--rotate").map(|degrees| match *degrees {
+                "90" => Rotation::Rotate90,
+                "180" => Rotation::Rotate180,
+                "270" => Rotation::Rotate270,
+                _ => panic!("{}", USAGE),
+            });
+            let mirror = options.get("--flip").map(|direction| match *direction {
+                "h" => Flip::Horizontal,
+                "v" => Flip::Vertical,
+                _ => panic!("{}", USAGE),
+            });
+            transform(filename, rotation, mirror, options.contains_key("--transpose"))
+        }
+        "crop" => {
+            let region = parse_region(options.get("--region").expect(USAGE));
+            crop_image(filename, region[0], region[1], region[2], region[3])
+        }
+        "downscale" => downscale_image(filename),
+        "adjust" => {
+            let brightness = options.get("--brightness").map_or(0.0, |amount| amount.parse().expect(USAGE));
+            let contrast = options.get("--contrast").map_or(1.0, |factor| factor.parse().expect(USAGE));
+            let saturation = match options.get("--saturation") {
+                Some(factor) => factor.parse().expect(USAGE),
+                None if options.contains_key("--desaturate") => 0.0,
+                None => 1.0,
+            };
+            adjust(filename, brightness, contrast, saturation)
+        }
+        "tile" => {
+            assert!(!files.is_empty(), "{}", USAGE);
+            let columns = options.get("--columns").map_or(files.len(), |n| n.parse().expect(USAGE));
+            tile_images(&files, columns, parse_colour(options.get("--fill").unwrap_or(&"0,0,0")))
+        }
+        "motion" => {
+            assert!(files.len() >= 2, "{}", USAGE);
+            match files[0] {
+                "append" => {
+                    let timestamp = options.get("--timestamp").map_or_else(
+                        || SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64,
+                        |ms| ms.parse().expect(USAGE));
+                    let keyframe_interval = options.get("--keyframe-interval").map_or(30, |n| n.parse().expect(USAGE));
+                    append_to_sequence(files[1], files.get(2).copied(), timestamp, keyframe_interval)
+                }
+                "list" => list_sequence(files[1]),
+                "extract" => extract_from_sequence(files[1], options.get("--frame").expect(USAGE).parse().expect(USAGE)),
+                _ => eprintln!("{}", USAGE),
+            }
+        }
         _ => {
-            eprintln!("Usage: rpeg -d [filename]\nrpeg -c [filename]")
+            eprintln!("{}", USAGE)
         }
     }
 }
+
+// Splits the arguments after the mode into options, mapped to their values, and filenames
+fn parse_args(args: &[String]) -> (HashMap<&str, &str>, Vec<&str>) {
+    let mut options = HashMap::new();
+    let mut files = vec![];
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if FLAGS.contains(&arg.as_str()) {
+            options.insert(arg.as_str(), "");
+        } else if arg.starts_with("--") {
+            options.insert(arg.as_str(), rest.next().expect(USAGE).as_str());
+        } else {
+            files.push(arg.as_str());
+        }
+    }
+    (options, files)
+}
+
+// Parses a comma separated list of numbers given as the value of an option
+fn parse_numbers(value: &str) -> Vec<usize> {
+    value.split(',').map(|num| num.parse().expect(USAGE)).collect()
+}
+
+// Parses a colour given as red,green,blue, with every channel out of 255
+fn parse_colour(value: &str) -> Rgb {
+    let colour = parse_numbers(value);
+    assert!(colour.len() == 3, "{}", USAGE);
+    assert!(colour.iter().all(|&channel| channel <= 255), "colour channels must be at most 255");
+    Rgb {red: colour[0] as u16, green: colour[1] as u16, blue: colour[2] as u16}
+}
+
+// Parses a region given as x,y,width,height
+fn parse_region(value: &str) -> Vec<usize> {
+    let region = parse_numbers(value);
+    assert!(region.len() == 4, "{}", USAGE);
+    region
+}
@@ -0,0 +1,77 @@
+use array2::Array2;
+use csc411_image::Rgb;
+
+/// Returns the peak signal-to-noise ratio in decibels between two images of the same size,
+/// measured over every colour channel scaled to [0, 1]. Channels above the denominator are
+/// clamped to it, as they are when the image is written. Identical images give infinity.
+///
+/// # Arguments
+///
+/// * `original`: an array2 that is storing the reference image
+/// * `original_denominator`: the maximum color value of `original`
+/// * `decoded`: an array2 that is storing the image to compare against the reference
+/// * `decoded_denominator`: the maximum color value of `decoded`
+pub fn psnr(original: &Array2<Rgb>, original_denominator: u16, decoded: &Array2<Rgb>, decoded_denominator: u16) -> f64 {
+    assert!(original.width() == decoded.width() && original.height() == decoded.height(),
+        "images must be the same size");
+    let channels = |pixel: &Rgb, denominator: u16| {
+        [pixel.red, pixel.green, pixel.blue].map(|value| value.min(denominator) as f64 / denominator as f64)
+    };
+    let squared_error = original.zip_with(decoded, |a, b| {
+        let (a, b) = (channels(a, original_denominator), channels(b, decoded_denominator));
+        (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f64>()
+    }).fold(0.0, |total, error| total + error);
+    let mse = squared_error / (3 * original.width() * original.height()) as f64;
+    10.0 * (1.0 / mse).log10()
+}
+
+// The side of the square windows that SSIM compares, and how far apart they start
+const SSIM_WINDOW: usize = 8;
+const SSIM_STEP: usize = 4;
+
+// Returns the luminance of every pixel scaled to [0, 1], with channels clamped to the denominator
+fn luminance(image: &Array2<Rgb>, denominator: u16) -> Array2<f64> {
+    image.map(|pixel| {
+        let [red, green, blue] = [pixel.red, pixel.green, pixel.blue].map(|value| value.min(denominator) as f64 / denominator as f64);
+        0.299 * red + 0.587 * green + 0.114 * blue
+    })
+}
+
+/// Returns the mean structural similarity between the luminance of two images of the same
+/// size, averaged over 8x8 windows that start every 4 pixels. An image smaller than a
+/// window is compared as a single window. Identical images give 1.0.
+///
+/// # Arguments
+///
+/// * `original`: an array2 that is storing the reference image
+/// * `original_denominator`: the maximum color value of `original`
+/// * `decoded`: an array2 that is storing the image to compare against the reference
+/// * `decoded_denominator`: the maximum color value of `decoded`
+pub fn ssim(original: &Array2<Rgb>, original_denominator: u16, decoded: &Array2<Rgb>, decoded_denominator: u16) -> f64 {
+    assert!(original.width() == decoded.width() && original.height() == decoded.height(),
+        "images must be the same size");
+    let (x, y) = (luminance(original, original_denominator), luminance(decoded, decoded_denominator));
+    let (c1, c2) = (0.01_f64.powi(2), 0.03_f64.powi(2));
+    let window_width = SSIM_WINDOW.min(x.width());
+    let window_height = SSIM_WINDOW.min(x.height());
+    let mut total = 0.0;
+    let mut windows = 0;
+    for top in (0..=x.height() - window_height).step_by(SSIM_STEP) {
+        for left in (0..=x.width() - window_width).step_by(SSIM_STEP) {
+            let pairs: Vec<(f64, f64)> = (top..top + window_height)
+                .flat_map(|r| (left..left + window_width).map(move |c| (r, c)))
+                .map(|(r, c)| (x[(r, c)], y[(r, c)]))
+                .collect();
+            let n = pairs.len() as f64;
+            let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+            let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+            let var_x = pairs.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>() / n;
+            let var_y = pairs.iter().map(|p| (p.1 - mean_y).powi(2)).sum::<f64>() / n;
+            let covariance = pairs.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum::<f64>() / n;
+            total += ((2.0 * mean_x * mean_y + c1) * (2.0 * covariance + c2))
+                / ((mean_x.powi(2) + mean_y.powi(2) + c1) * (var_x + var_y + c2));
+            windows += 1;
+        }
+    }
+    total / windows as f64
+}
@@ -0,0 +1,47 @@
+use crate::block::component_video_to_blocks;
+use crate::dct::to_cosine_space;
+use crate::pixel_converter::rgb_image_to_component_video;
+use crate::quantization::{convert_to_bits, BitValue};
+use array2::Array2;
+use csc411_image::Rgb;
+
+/// Returns the quantized values of a 2x2 block filled with a single colour, by passing
+/// it through the same steps as the compressor
+///
+/// # Arguments
+///
+/// * `colour`: the colour of every pixel in the block
+/// * `denominator`: the maximum color value of `colour`
+pub fn solid_codeword(colour: Rgb, denominator: u16) -> BitValue {
+    let block = Array2::from_blank_state(colour, 2, 2);
+    convert_to_bits(to_cosine_space(component_video_to_blocks(
+        rgb_image_to_component_video(block, denominator))))[(0, 0)]
+        .clone()
+}
+
+/// Places compressed images into a grid without decoding them. Every cell of the grid is
+/// as large as the largest image, and any space an image does not cover is filled with
+/// the `fill` block.
+///
+/// # Arguments
+///
+/// * `images`: array2s that hold all of the bit values per 2x2 block, in row-major order
+///   of where they are placed
+/// * `columns`: the number of images placed side by side in each row of the grid
+/// * `fill`: the quantized values to use for any uncovered blocks
+pub fn tile(images: Vec<Array2<BitValue>>, columns: usize, fill: &BitValue) -> Array2<BitValue> {
+    assert!(columns > 0, "a mosaic needs at least one column");
+    let cell_width = images.iter().map(|image| image.width()).max().unwrap_or(0);
+    let cell_height = images.iter().map(|image| image.height()).max().unwrap_or(0);
+    let rows = images.len().div_ceil(columns);
+    let mut mosaic = Array2::from_blank_state(fill.clone(), cell_width * columns, cell_height * rows);
+    for (index, image) in images.into_iter().enumerate() {
+        // the top left block of the cell this image is placed in
+        let top = (index / columns) * cell_height;
+        let left = (index % columns) * cell_width;
+        for (r, c, block) in image.iter_row_major() {
+            mosaic[(top + r, left + c)] = block.clone();
+        }
+    }
+    mosaic
+}
@@ -0,0 +1,241 @@
+use crate::codeword::{pack_codewords, unpack_codewords};
+use crate::quantization::BitValue;
+use array2::Array2;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+// The first line of every motion rpeg container
+const FORMAT: &str = "Motion rpeg format 2";
+
+/// How a frame is stored in a motion rpeg container. A `Key` frame holds every codeword,
+/// while a `Delta` frame holds a bitmap of which blocks changed since the previous frame,
+/// followed by only the codewords of those blocks.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FrameKind {
+    Key,
+    Delta,
+}
+
+/// A `FrameEntry` is one line of the frame index table of a motion rpeg container. It
+/// holds the timestamp of the frame, its width and height in pixels, the offset of its
+/// data in bytes from the start of the frame data, and how the frame is stored.
+#[derive(Clone)]
+pub struct FrameEntry {
+    pub timestamp: u64,
+    pub width: usize,
+    pub height: usize,
+    pub offset: u64,
+    pub kind: FrameKind,
+}
+
+/// A `Frame` is a single compressed image of a sequence along with its timestamp
+#[derive(Clone)]
+pub struct Frame {
+    pub timestamp: u64,
+    pub codewords: Array2<BitValue>,
+}
+
+// Reads the header and the frame index table, returning the table, the most frames stored
+// between keyframes, and the length in bytes of everything before the frame data
+fn read_index_table(reader: &mut impl BufRead) -> (Vec<FrameEntry>, usize, u64) {
+    let mut line = String::new();
+    let mut header_len = reader.read_line(&mut line).unwrap();
+    assert!(line.trim_end() == FORMAT, "not a motion rpeg container");
+    line.clear();
+    header_len += reader.read_line(&mut line).unwrap();
+    let counts: Vec<usize> = line.split_whitespace().map(|num| num.parse().unwrap()).collect();
+    assert!(counts.len() == 2, "badly formatted motion rpeg header");
+    let mut entries = vec![];
+    for _ in 0..counts[0] {
+        line.clear();
+        header_len += reader.read_line(&mut line).unwrap();
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        assert!(fields.len() == 5, "badly formatted frame index table");
+        entries.push(FrameEntry {
+            timestamp: fields[0].parse().unwrap(),
+            width: fields[1].parse().unwrap(),
+            height: fields[2].parse().unwrap(),
+            offset: fields[3].parse().unwrap(),
+            kind: if fields[4] == "P" { FrameKind::Delta } else { FrameKind::Key },
+        });
+    }
+    (entries, counts[1], header_len as u64)
+}
+
+// Reads `count` codewords of 4 bytes each
+fn read_codewords(reader: &mut impl Read, count: usize) -> Vec<[u8; 4]> {
+    let mut bytes = vec![0_u8; count * 4];
+    reader.read_exact(&mut bytes).unwrap();
+    bytes.chunks_exact(4).map(|codeword| codeword.try_into().unwrap()).collect()
+}
+
+// Rebuilds the codewords of a frame by reading the keyframe it depends on and applying
+// every delta frame after it in order
+fn reconstruct(reader: &mut (impl Read + Seek), data_start: u64, entries: &[FrameEntry], index: usize) -> Vec<[u8; 4]> {
+    let keyframe = (0..=index).rev().find(|&i| entries[i].kind == FrameKind::Key).expect("no keyframe before the frame");
+    let mut codewords = vec![];
+    for entry in &entries[keyframe..=index] {
+        let count = entry.width/2 * entry.height/2;
+        reader.seek(SeekFrom::Start(data_start + entry.offset)).unwrap();
+        match entry.kind {
+            FrameKind::Key => codewords = read_codewords(reader, count),
+            FrameKind::Delta => {
+                let mut bitmap = vec![0_u8; count.div_ceil(8)];
+                reader.read_exact(&mut bitmap).unwrap();
+                for (block, codeword) in codewords.iter_mut().enumerate() {
+                    if bitmap[block/8] & (0x80 >> (block % 8)) != 0 {
+                        *codeword = read_codewords(reader, 1)[0];
+                    }
+                }
+            }
+        }
+    }
+    codewords
+}
+
+// Encodes a frame as a bitmap of the blocks whose codewords differ from the previous frame,
+// followed by the codewords of only those blocks
+fn encode_delta(previous: &[[u8; 4]], current: &[[u8; 4]]) -> Vec<u8> {
+    let mut bitmap = vec![0_u8; current.len().div_ceil(8)];
+    let mut changed = vec![];
+    for (block, (old, new)) in previous.iter().zip(current).enumerate() {
+        if old != new {
+            bitmap[block/8] |= 0x80 >> (block % 8);
+            changed.extend_from_slice(new);
+        }
+    }
+    bitmap.extend(changed);
+    bitmap
+}
+
+/// Returns the frame index table of a motion rpeg container
+///
+/// # Arguments
+///
+/// * `path`: the file path of the container
+pub fn list_frames(path: &str) -> Vec<FrameEntry> {
+    read_index_table(&mut BufReader::new(File::open(path).unwrap())).0
+}
+
+/// Reads a single frame out of a motion rpeg container. Only the data of the keyframe it
+/// depends on and the delta frames between them is read, by seeking directly to each.
+///
+/// # Arguments
+///
+/// * `path`: the file path of the container
+/// * `index`: the position of the frame in the sequence, starting at 0
+pub fn read_frame(path: &str, index: usize) -> Frame {
+    let mut reader = BufReader::new(File::open(path).unwrap());
+    let (entries, _, header_len) = read_index_table(&mut reader);
+    let entry = entries.get(index).unwrap_or_else(|| panic!("the container has {} frames", entries.len()));
+    let codewords = reconstruct(&mut reader, header_len, &entries, index);
+    Frame {
+        timestamp: entry.timestamp,
+        codewords: unpack_codewords(&codewords, entry.width/2, entry.height/2),
+    }
+}
+
+/// Adds a frame to the end of a motion rpeg container, creating the container if it does
+/// not exist yet. The frame is stored as a delta against the previous frame unless it is
+/// the first frame, its size differs from the previous frame, `keyframe_interval` frames
+/// have passed since the last keyframe, or the delta would be no smaller than a keyframe.
+/// The frame index table sits before the frame data, so the container is rewritten.
+///
+/// # Arguments
+///
+/// * `path`: the file path of the container
+/// * `frame`: the frame to add
+/// * `keyframe_interval`: the most frames from one keyframe to the next, only used when
+///   the container is created
+pub fn append_frame(path: &str, frame: Frame, keyframe_interval: usize) {
+    let (mut entries, mut interval, mut data) = (vec![], keyframe_interval.max(1), vec![]);
+    if Path::new(path).exists() {
+        let mut reader = BufReader::new(File::open(path).unwrap());
+        (entries, interval, _) = read_index_table(&mut reader);
+        reader.read_to_end(&mut data).unwrap();
+    }
+    let (width, height) = (frame.codewords.width()*2, frame.codewords.height()*2);
+    let codewords = pack_codewords(&frame.codewords);
+    let mut body = codewords.concat();
+    let mut kind = FrameKind::Key;
+    if let Some(last) = entries.last() {
+        let since_keyframe = entries.iter().rev().take_while(|entry| entry.kind == FrameKind::Delta).count() + 1;
+        if last.width == width && last.height == height && since_keyframe < interval {
+            let previous = reconstruct(&mut Cursor::new(&data), 0, &entries, entries.len() - 1);
+            let delta = encode_delta(&previous, &codewords);
+            if delta.len() < body.len() {
+                (body, kind) = (delta, FrameKind::Delta);
+            }
+        }
+    }
+    entries.push(FrameEntry {timestamp: frame.timestamp, width, height, offset: data.len() as u64, kind});
+    data.extend(body);
+
+    let mut file = File::create(path).unwrap();
+    writeln!(file, "{}", FORMAT).unwrap();
+    writeln!(file, "{} {}", entries.len(), interval).unwrap();
+    for entry in &entries {
+        let kind = if entry.kind == FrameKind::Delta { "P" } else { "I" };
+        writeln!(file, "{} {} {} {} {}", entry.timestamp, entry.width, entry.height, entry.offset, kind).unwrap();
+    }
+    file.write_all(&data).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A frame of `width` x `height` blocks whose codewords all depend on `seed`
+    fn frame(timestamp: u64, width: usize, height: usize, seed: usize) -> Frame {
+        let codewords = Array2::from_fn(width, height, |row, col| {
+            let n = row * 7 + col * 3 + seed;
+            BitValue {a: (n * 37 % 512) as u64, b: (n % 31) as i64 - 15, c: (n * 5 % 31) as i64 - 15,
+                d: (n * 11 % 31) as i64 - 15, index_pb: n % 16, index_pr: n * 3 % 16}
+        });
+        Frame {timestamp, codewords}
+    }
+
+    // Returns a copy of `frame` at a new timestamp with a single block changed
+    fn with_block_changed(frame: &Frame, timestamp: u64, row: usize, col: usize) -> Frame {
+        let mut codewords = frame.codewords.clone();
+        codewords[(row, col)].a = (codewords[(row, col)].a + 1) % 512;
+        Frame {timestamp, codewords}
+    }
+
+    #[test]
+    fn appended_frames_read_back_exactly() {
+        let path = std::env::temp_dir().join(format!("rpeg-motion-{}.mrpeg", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+        let mut frames = vec![frame(0, 4, 3, 0)];
+        for (timestamp, row, col) in [(10, 0, 0), (20, 2, 3), (30, 1, 1), (40, 0, 3)] {
+            frames.push(with_block_changed(frames.last().unwrap(), timestamp, row, col));
+        }
+        // a new size, then a frame where every block changes, then one where none do
+        frames.push(frame(50, 3, 2, 1));
+        frames.push(frame(60, 3, 2, 2));
+        frames.push(Frame {timestamp: 70, ..frames.last().unwrap().clone()});
+        for frame in &frames {
+            append_frame(path, frame.clone(), 3);
+        }
+
+        let entries = list_frames(path);
+        let (key, delta) = (FrameKind::Key, FrameKind::Delta);
+        // the fourth frame is the third after the first keyframe, so it starts a new one
+        let kinds = [key, delta, delta, key, delta, key, key, delta];
+        assert_eq!(entries.len(), frames.len());
+        for ((entry, frame), kind) in entries.iter().zip(&frames).zip(kinds) {
+            assert!(entry.kind == kind, "frame {} is stored the wrong way", entry.timestamp);
+            assert_eq!((entry.timestamp, entry.width, entry.height),
+                (frame.timestamp, frame.codewords.width() * 2, frame.codewords.height() * 2));
+        }
+        for (index, frame) in frames.iter().enumerate() {
+            let read = read_frame(path, index);
+            assert_eq!(read.timestamp, frame.timestamp);
+            assert_eq!((read.codewords.width(), read.codewords.height()), (frame.codewords.width(), frame.codewords.height()));
+            assert_eq!(pack_codewords(&read.codewords), pack_codewords(&frame.codewords));
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -35,7 +35,7 @@ fn rgb_to_component_video(pixel: Rgb, denominator: u16) -> ComponentVideo {
 }
 
 // Converts a ComponentVideo pixel to an Rgb pixel
-fn component_video_to_rgb(pixel: ComponentVideo, denominator: u16) -> Rgb {
+pub(crate) fn component_video_to_rgb(pixel: ComponentVideo, denominator: u16) -> Rgb {
     let f32r = 1.0 * pixel.y + 0.0 * pixel.pb + 1.402 * pixel.pr;
     let f32g = 1.0 * pixel.y - 0.344136 * pixel.pb - 0.714136 * pixel.pr;
     let f32b = 1.0 * pixel.y + 1.772 * pixel.pb + 0.0 * pixel.pr;
@@ -48,10 +48,8 @@ fn component_video_to_rgb(pixel: ComponentVideo, denominator: u16) -> Rgb {
 ///
 /// * `image`: an array2 that is storing an image with Rgb pixels
 /// * `denominator`: the maximum color value of the image
-pub fn rgb_image_to_component_video(mut image: Array2<Rgb>, denominator: u16) ->  Array2<ComponentVideo> {
-    let component_video_vec:Vec<_>= image.iter_row_major_mut().map(|(_r, _c, pixel)| (
-        rgb_to_component_video(pixel.clone(), denominator))).collect();
-    Array2::from_row_major(component_video_vec, image.width(), image.height())
+pub fn rgb_image_to_component_video(image: Array2<Rgb>, denominator: u16) ->  Array2<ComponentVideo> {
+    image.map(|pixel| rgb_to_component_video(pixel.clone(), denominator))
 }
 
 /// Transforms every Component Video pixel in the image into an Rgb pixel
@@ -60,8 +58,6 @@ pub fn rgb_image_to_component_video(mut image: Array2<Rgb>, denominator: u16) ->
 ///
 /// * `image`: an array2 that is storing an image with ComponentVideo pixels
 /// * `denominator`: the maximum color value of the image
-pub fn component_video_image_to_rgb(mut image: Array2<ComponentVideo>, denominator: u16) -> Array2<Rgb> {
-    let rgb_vec:Vec<_> = image.iter_row_major_mut().map(|(_r, _c, pixel)| (
-        component_video_to_rgb(pixel.clone(), denominator))).collect();
-    Array2::from_row_major(rgb_vec, image.width(), image.height())
+pub fn component_video_image_to_rgb(image: Array2<ComponentVideo>, denominator: u16) -> Array2<Rgb> {
+    image.map(|pixel| component_video_to_rgb(pixel.clone(), denominator))
 }
\ No newline at end of file
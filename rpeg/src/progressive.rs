@@ -0,0 +1,140 @@
+use bitpack::bitpack::{newu, getu};
+use array2::Array2;
+use crate::codeword::{pack_codewords, unpack_codewords};
+use crate::quantization::BitValue;
+use std::io::{BufRead, BufReader, Write};
+
+// The first line of every progressive rpeg file
+const FORMAT: &str = "Progressive image format 2";
+
+// Appends bit fields to a stream of bytes, most significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    word: u64,
+    bits: u64,
+}
+
+impl BitWriter {
+    // Appends the low `width` bits of `value`
+    fn push(&mut self, width: u64, value: u64) {
+        self.word = newu(self.word << width, width, 0, value).unwrap();
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.bytes.push(getu(self.word, 8, self.bits) as u8);
+        }
+    }
+
+    // Pads the stream with zeros up to the next byte
+    fn pad(&mut self) {
+        if self.bits > 0 {
+            self.push(8 - self.bits, 0);
+        }
+    }
+}
+
+// Reads bit fields from a stream of bytes, most significant bit first
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    // Reads the next `width` bits, or returns `None` if the stream ends before them
+    fn read(&mut self, width: u64) -> Option<u64> {
+        if self.position + width as usize > self.bytes.len() * 8 {
+            return None;
+        }
+        let mut value = 0;
+        for _ in 0..width {
+            let bit = getu(self.bytes[self.position / 8] as u64, 1, 7 - (self.position % 8) as u64);
+            value = value << 1 | bit;
+            self.position += 1;
+        }
+        Some(value)
+    }
+
+    // Skips to the start of the next byte
+    fn align(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+}
+
+/// Outputs the quantized 2x2 blocks to stdout in two passes. The first pass holds `a`,
+/// `index_pb`, and `index_pr` of every block, and the second pass holds `b`, `c`, and `d`
+/// of every block, so the start of the file alone is enough for a complete preview.
+///
+/// # Arguments
+///
+/// * `quantized_block`: an array2 of 2x2 blocks of quantized values
+pub fn output_progressive(quantized_block: Array2<BitValue>) {
+    write_progressive(&quantized_block, &mut std::io::stdout());
+}
+
+// Writes the header and both passes of the quantized 2x2 blocks to `output`
+pub(crate) fn write_progressive(quantized_block: &Array2<BitValue>, output: &mut impl Write) {
+    let codewords: Vec<u64> = pack_codewords(quantized_block).into_iter()
+        .map(|bytes| u32::from_be_bytes(bytes) as u64).collect();
+    let mut writer = BitWriter {bytes: vec![], word: 0, bits: 0};
+    // the first pass is the 9 bit `a` followed by the two 4 bit chroma indices
+    for &codeword in &codewords {
+        writer.push(9, getu(codeword, 9, 23));
+        writer.push(8, getu(codeword, 8, 0));
+    }
+    writer.pad();
+    // the second pass is the three 5 bit coefficients `b`, `c`, and `d`
+    for &codeword in &codewords {
+        writer.push(15, getu(codeword, 15, 8));
+    }
+    writer.pad();
+
+    writeln!(output, "{}", FORMAT).unwrap();
+    writeln!(output, "{} {}", quantized_block.width()*2, quantized_block.height()*2).unwrap();
+    output.write_all(&writer.bytes).unwrap();
+}
+
+/// Reads a progressive file from standard in or a file, and converts it back to 2x2 blocks
+/// of quantized values. The file may be cut off anywhere after the first pass; any block
+/// whose second pass has not arrived yet gets zero `b`, `c`, and `d`.
+///
+/// # Arguments
+///
+/// * `file_path`: An optional file path, if `None` read from standard in
+pub fn read_progressive(file_path: Option<&str>) -> Array2<BitValue> {
+    let reader: Box<dyn BufRead> = match file_path {
+        Some(path) => Box::new(BufReader::new(std::fs::File::open(path).unwrap())),
+        None => Box::new(BufReader::new(std::io::stdin())),
+    };
+    parse_progressive(reader)
+}
+
+// Reads a progressive file from `reader`, which may be cut off anywhere after the first pass
+pub(crate) fn parse_progressive(mut reader: impl BufRead) -> Array2<BitValue> {
+    let mut format_line = String::new();
+    let mut size_line = String::new();
+    reader.read_line(&mut format_line).unwrap();
+    reader.read_line(&mut size_line).unwrap();
+    assert!(format_line.trim_end() == FORMAT, "not a progressive rpeg file");
+    let size: Vec<usize> = size_line.split_whitespace().map(|num| num.parse().unwrap()).collect();
+    assert!(size.len() == 2, "badly formatted rpeg header");
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes).unwrap();
+
+    let block_count = size[0]/2 * size[1]/2;
+    let mut bits = BitReader {bytes: &bytes, position: 0};
+    let mut codewords = vec![];
+    for _ in 0..block_count {
+        let a = bits.read(9).expect("the first pass is incomplete");
+        let chroma = bits.read(8).expect("the first pass is incomplete");
+        codewords.push(newu(newu(0, 9, 23, a).unwrap(), 8, 0, chroma).unwrap());
+    }
+    bits.align();
+    for codeword in codewords.iter_mut() {
+        match bits.read(15) {
+            Some(detail) => *codeword = newu(*codeword, 15, 8, detail).unwrap(),
+            None => break,
+        }
+    }
+    let codewords: Vec<[u8; 4]> = codewords.into_iter().map(|codeword| (codeword as u32).to_be_bytes()).collect();
+    unpack_codewords(&codewords, size[0]/2, size[1]/2)
+}
@@ -1,6 +1,9 @@
 use crate::block::Block;
+use crate::dct::cosine_to_pixels;
+use crate::pixel_converter::{component_video_to_rgb, ComponentVideo};
 use array2::Array2;
 use csc411_arith::*;
+use csc411_image::Rgb;
 
 /// A `BitValues` stores the different bit integers where `a` is a 9 bit unsigned scaled integer,
 /// `b` is a 5 bit signed scaled integer, `c` is a 5 bit signed scaled integer, `d` is a 5 bit
@@ -63,6 +66,145 @@ fn decode(n: i64) -> f64 {
     n as f64/50.0
 }
 
+/// Options for how the floating point values of each 2x2 block are quantized
+#[derive(Clone, Copy, Default)]
+pub struct QuantizationParams {
+    /// Instead of rounding every value to its nearest quantized value on its own, search
+    /// the quantized values around them for those whose decoded RGB pixels are closest to
+    /// the block's unquantized RGB pixels
+    pub rate_distortion: bool,
+    /// Run the same search, but measure the error the way the eye notices it: chroma
+    /// errors count for far less than luminance errors, an error in the average of a block
+    /// counts for more than one in its detail, and luminance errors count for less in
+    /// blocks whose own `b`, `c`, and `d` show enough texture to mask them
+    pub perceptual: bool,
+}
+
+// How much a chroma error counts compared to a luminance error of the same size in a
+// flat block
+const CHROMA_WEIGHT: f64 = 0.004;
+// How much an error in the average luminance of a block counts compared to one in its
+// detail. The eye sees structure across many blocks at once, and there a wrong average
+// shows as a step against the neighbouring blocks.
+const AVERAGE_WEIGHT: f64 = 8.0;
+// The energy of `b`, `c`, and `d` at which texture halves the weight of luminance errors,
+// about that of a single gradient of 0.045
+const MASKING_ENERGY: f64 = 0.002;
+
+// Rounds every value of a block in cosine space to its nearest quantized value
+fn quantize_block(block: &Block) -> BitValue {
+    let bcd = b_c_d_to_bits(block.luminance[1], block.luminance[2], block.luminance[3]);
+    let pb_pr = pb_pr_avg_to_bits(block.pb_avg, block.pr_avg);
+    BitValue {a: a_to_bits(block.luminance[0]), b: bcd.0, c: bcd.1, d: bcd.2, 
+        index_pb: pb_pr.0, index_pr: pb_pr.1}
+}
+
+// Converts the quantized values of a block back to cosine space
+fn dequantize_block(bit_block: &BitValue) -> Block {
+    let bcd = b_c_d_to_f64(bit_block.b, bit_block.c, bit_block.d);
+    let pb_pr = pb_pr_avg_to_f32(bit_block.index_pb, bit_block.index_pr);
+    Block {luminance: [a_to_f64(bit_block.a), bcd.0, bcd.1, bcd.2], pb_avg: pb_pr.0, 
+    pr_avg: pb_pr.1}
+}
+
+// Returns the four RGB pixels, with denominator 255, that a block in cosine space decodes to
+fn block_to_rgb(block: &Block) -> [Rgb; 4] {
+    cosine_to_pixels(block.luminance).map(|y| {
+        let pixel = component_video_to_rgb(ComponentVideo {y: y as f32, pb: block.pb_avg, pr: block.pr_avg}, 255);
+        // the image writer clamps every channel to the denominator
+        Rgb {red: pixel.red.min(255), green: pixel.green.min(255), blue: pixel.blue.min(255)}
+    })
+}
+
+// Returns the sum of squared channel differences between two sets of pixels
+fn rgb_error(a: &[Rgb; 4], b: &[Rgb; 4]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| {
+        [(a.red, b.red), (a.green, b.green), (a.blue, b.blue)].iter()
+            .map(|&(x, y)| (x as f64 - y as f64).powi(2)).sum::<f64>()
+    }).sum()
+}
+
+// Returns the error between two sets of pixels as the eye notices it. The luminance error of
+// each pixel is split into the error of the block average and the error of the detail around
+// it, the average error is weighted up, and both are scaled down by `masking`, the way texture
+// around an error hides it. The chroma error, which the eye resolves far less finely, is added
+// at a small fixed weight.
+fn perceptual_error(a: &[Rgb; 4], b: &[Rgb; 4], masking: f64) -> f64 {
+    let differences = a.iter().zip(b).map(|(a, b)| {
+        [a.red as f64 - b.red as f64, a.green as f64 - b.green as f64, a.blue as f64 - b.blue as f64]
+    });
+    let mut luma_errors = [0.0; 4];
+    let mut chroma_error = 0.0;
+    for (i, [red, green, blue]) in differences.enumerate() {
+        luma_errors[i] = 0.299 * red + 0.587 * green + 0.114 * blue;
+        chroma_error += (red - luma_errors[i]).powi(2) + (green - luma_errors[i]).powi(2) + (blue - luma_errors[i]).powi(2);
+    }
+    let average_error = luma_errors.iter().sum::<f64>() / 4.0;
+    let detail_error: f64 = luma_errors.iter().map(|error| (error - average_error).powi(2)).sum();
+    masking * (AVERAGE_WEIGHT * 4.0 * average_error.powi(2) + detail_error) + CHROMA_WEIGHT * chroma_error
+}
+
+// Returns a copy of `bit_block` with one of its six values moved by `step`, or `None`
+// if that moves the value out of the range the encoder uses for it
+fn step_value(bit_block: &BitValue, value: usize, step: i64) -> Option<BitValue> {
+    let mut candidate = bit_block.clone();
+    let (current, max, min) = match value {
+        0 => (bit_block.a as i64, 511, 0),
+        1 => (bit_block.b, 15, -15),
+        2 => (bit_block.c, 15, -15),
+        3 => (bit_block.d, 15, -15),
+        4 => (bit_block.index_pb as i64, 15, 0),
+        _ => (bit_block.index_pr as i64, 15, 0),
+    };
+    let stepped = current + step;
+    if stepped < min || stepped > max {
+        return None;
+    }
+    match value {
+        0 => candidate.a = stepped as u64,
+        1 => candidate.b = stepped,
+        2 => candidate.c = stepped,
+        3 => candidate.d = stepped,
+        4 => candidate.index_pb = stepped as usize,
+        _ => candidate.index_pr = stepped as usize,
+    }
+    Some(candidate)
+}
+
+// Starting from the nearest quantized values, repeatedly moves one value at a time to
+// whichever neighbouring quantized value lowers the error of the decoded RGB pixels the
+// most, until no single move helps. This lets `a` and the other coefficients make up
+// for gradients that were clamped at +/-0.3.
+fn optimize_block(block: &Block, perceptual: bool) -> BitValue {
+    let target = block_to_rgb(block);
+    let texture: f64 = block.luminance[1..].iter().map(|n| n.powi(2)).sum();
+    let masking = 1.0 / (1.0 + texture / MASKING_ENERGY);
+    let cost = |bit_block: &BitValue| {
+        let decoded = block_to_rgb(&dequantize_block(bit_block));
+        if perceptual { perceptual_error(&target, &decoded, masking) } else { rgb_error(&target, &decoded) }
+    };
+    let mut best = quantize_block(block);
+    let mut best_cost = cost(&best);
+    // each pass can only lower the cost, so this bound is only a safeguard
+    for _ in 0..16 {
+        let mut improved = false;
+        for value in 0..6 {
+            for step in [-2, -1, 1, 2] {
+                if let Some(candidate) = step_value(&best, value, step) {
+                    let candidate_cost = cost(&candidate);
+                    if candidate_cost < best_cost {
+                        (best, best_cost, improved) = (candidate, candidate_cost, true);
+                    }
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    best
+}
+
 /// Converts the floating point values from the 2x2 blocks to signed and unsigned
 /// integer values
 ///
@@ -70,14 +212,29 @@ fn decode(n: i64) -> f64 {
 ///
 /// * `blocks`: an array2 that holds all 2x2 blocks in cosine space
 pub fn convert_to_bits(cs_blocks: Array2<Block>) -> Array2<BitValue> {
-    let mut bitval_vec = vec![];
-    for (_r, _c, block) in cs_blocks.iter_row_major() {
-        let bcd = b_c_d_to_bits(block.luminance[1], block.luminance[2], block.luminance[3]);
-        let pb_pr = pb_pr_avg_to_bits(block.pb_avg, block.pr_avg);
-        bitval_vec.push(BitValue {a: a_to_bits(block.luminance[0]), b: bcd.0, c: bcd.1, d: bcd.2, 
-            index_pb: pb_pr.0, index_pr: pb_pr.1});
-    }
-    Array2::from_row_major(bitval_vec, cs_blocks.width(), cs_blocks.height())
+    convert_to_bits_with(cs_blocks, QuantizationParams::default())
+}
+
+/// Converts the floating point values from the 2x2 blocks to signed and unsigned
+/// integer values, quantizing them as `params` asks
+///
+/// # Arguments
+///
+/// * `blocks`: an array2 that holds all 2x2 blocks in cosine space
+/// * `params`: options for how the values are quantized
+pub fn convert_to_bits_with(cs_blocks: Array2<Block>, params: QuantizationParams) -> Array2<BitValue> {
+    let quantize = |block: &Block| if params.rate_distortion || params.perceptual {
+        optimize_block(block, params.perceptual)
+    } else {
+        quantize_block(block)
+    };
+    // every block is quantized on its own, and the search makes this the slowest stage,
+    // so it is spread across threads when the `rayon` feature is on
+    #[cfg(feature = "rayon")]
+    let bit_blocks = cs_blocks.par_map(quantize);
+    #[cfg(not(feature = "rayon"))]
+    let bit_blocks = cs_blocks.map(quantize);
+    bit_blocks
 }
 
 /// Converts the signed and unsigned integer values from the 2x2 blocks back
@@ -87,12 +244,18 @@ pub fn convert_to_bits(cs_blocks: Array2<Block>) -> Array2<BitValue> {
 ///
 /// * `blocks`: an array2 that holds all of the bit values per 2x2 block
 pub fn convert_to_cs(bit_blocks: Array2<BitValue>) -> Array2<Block> {
-    let mut cs_block_vec = vec![];
-    for (_r, _c, bit_block) in bit_blocks.iter_row_major() {
-        let bcd = b_c_d_to_f64(bit_block.b, bit_block.c, bit_block.d);
+    bit_blocks.map(dequantize_block)
+}
+
+/// Converts the quantized values of every 2x2 block to a single component video pixel
+/// holding the average luminance and chroma of the block, ignoring `b`, `c`, and `d`
+///
+/// # Arguments
+///
+/// * `blocks`: an array2 that holds all of the bit values per 2x2 block
+pub fn convert_to_averages(bit_blocks: Array2<BitValue>) -> Array2<ComponentVideo> {
+    bit_blocks.map(|bit_block| {
         let pb_pr = pb_pr_avg_to_f32(bit_block.index_pb, bit_block.index_pr);
-        cs_block_vec.push(Block {luminance: [a_to_f64(bit_block.a), bcd.0, bcd.1, bcd.2], pb_avg: pb_pr.0, 
-        pr_avg: pb_pr.1});
-    }
-    Array2::from_row_major(cs_block_vec, bit_blocks.width(), bit_blocks.height())
+        ComponentVideo {y: a_to_f64(bit_block.a) as f32, pb: pb_pr.0, pr: pb_pr.1}
+    })
 }
\ No newline at end of file
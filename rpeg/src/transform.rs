@@ -0,0 +1,119 @@
+use crate::block::Block;
+use crate::dct::to_cosine_space;
+use crate::quantization::{convert_to_bits, convert_to_cs, BitValue};
+use array2::{Array2, EdgePolicy};
+
+/// A clockwise rotation by a multiple of 90 degrees
+#[derive(Clone, Copy)]
+pub enum Rotation {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+/// A mirroring of the image, `Horizontal` swaps left and right and `Vertical` swaps
+/// top and bottom
+#[derive(Clone, Copy)]
+pub enum Flip {
+    Horizontal,
+    Vertical,
+}
+
+// Negates a 5 bit signed coefficient. The encoder clamps coefficients to [-15, 15], so
+// this is exact for any compressed image; a stray -16 is saturated to fit the field.
+fn negate(n: i64) -> i64 {
+    (-n).min(15)
+}
+
+// Swapping the top-right and bottom-left pixels swaps the vertical and horizontal gradients
+fn transpose_block(block: &BitValue) -> BitValue {
+    BitValue { b: block.c, c: block.b, ..block.clone() }
+}
+
+// Swapping left and right pixels reverses the horizontal gradient and the diagonal
+fn flip_block_horizontal(block: &BitValue) -> BitValue {
+    BitValue { c: negate(block.c), d: negate(block.d), ..block.clone() }
+}
+
+// Swapping top and bottom pixels reverses the vertical gradient and the diagonal
+fn flip_block_vertical(block: &BitValue) -> BitValue {
+    BitValue { b: negate(block.b), d: negate(block.d), ..block.clone() }
+}
+
+/// Transposes the image by swapping block rows with block columns and rewriting the
+/// coefficients of every block, without decoding it
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+pub fn transpose(codewords: Array2<BitValue>) -> Array2<BitValue> {
+    codewords.transpose().map(transpose_block)
+}
+
+/// Mirrors the image by reversing the order of the blocks and rewriting the coefficients
+/// of every block, without decoding it
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+/// * `flip`: the direction to mirror the image in
+pub fn flip(codewords: Array2<BitValue>, flip: Flip) -> Array2<BitValue> {
+    match flip {
+        Flip::Horizontal => codewords.flip_horizontal().map(flip_block_horizontal),
+        Flip::Vertical => codewords.flip_vertical().map(flip_block_vertical),
+    }
+}
+
+/// Rotates the image clockwise without decoding it. The blocks are rotated as a grid, and
+/// the coefficients of every block are rewritten as the transpose and flips that make up
+/// the rotation, so no quantization loss is added.
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+/// * `rotation`: how far to rotate the image
+pub fn rotate(codewords: Array2<BitValue>, rotation: Rotation) -> Array2<BitValue> {
+    match rotation {
+        Rotation::Rotate90 => codewords.rotate90().map(|block| flip_block_horizontal(&transpose_block(block))),
+        Rotation::Rotate180 => codewords.rotate180().map(|block| flip_block_vertical(&flip_block_horizontal(block))),
+        Rotation::Rotate270 => codewords.rotate270().map(|block| flip_block_vertical(&transpose_block(block))),
+    }
+}
+
+/// Extracts the `width` x `height` rectangle of blocks whose top left block is at
+/// (`row`, `col`) without decoding it
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+/// * `row`: the block row of the top left block of the rectangle
+/// * `col`: the block column of the top left block of the rectangle
+/// * `height`: the number of block rows in the rectangle
+/// * `width`: the number of block columns in the rectangle
+pub fn crop(codewords: Array2<BitValue>, row: usize, col: usize, height: usize, width: usize) -> Array2<BitValue> {
+    assert!(row + height <= codewords.height() && col + width <= codewords.width(),
+        "crop lies outside the {}x{} blocks of the image", codewords.width(), codewords.height());
+    codewords.crop(row, col, height, width)
+}
+
+/// Halves the size of the image without decoding it to pixels. The average luminances
+/// of each 2x2 group of blocks become the four luminances of one new block, whose chroma
+/// is the average of the group's chroma. A trailing odd row or column of blocks is dropped.
+///
+/// # Arguments
+///
+/// * `codewords`: an array2 that holds all of the bit values per 2x2 block
+pub fn downscale(codewords: Array2<BitValue>) -> Array2<BitValue> {
+    let cs_blocks = convert_to_cs(codewords);
+    let mut block_vec = vec![];
+    for (_r, _c, tile) in cs_blocks.blocks(2, 2, EdgePolicy::Skip) {
+        let group = [&tile[(0, 0)], &tile[(0, 1)], &tile[(1, 0)], &tile[(1, 1)]];
+        block_vec.push(Block {
+            luminance: group.map(|block| block.luminance[0]),
+            pb_avg: group.iter().map(|block| block.pb_avg).sum::<f32>() / 4.0,
+            pr_avg: group.iter().map(|block| block.pr_avg).sum::<f32>() / 4.0,
+        });
+    }
+    convert_to_bits(to_cosine_space(
+        Array2::from_row_major(block_vec, cs_blocks.width()/2, cs_blocks.height()/2)))
+}